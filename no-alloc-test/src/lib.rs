@@ -0,0 +1,2 @@
+//! Hosts the `no_alloc` integration test, which installs an allocation-counting
+//! global allocator. See `tests/no_alloc.rs`.
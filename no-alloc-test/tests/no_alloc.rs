@@ -0,0 +1,71 @@
+//! Parsing must never allocate on the heap. Anything that needs to allocate,
+//! e.g. `try_parse_verbose`, must stay on a separate entry point.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use http_content_range::{ContentRange, ParseOptions};
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = Cell::new(0);
+}
+
+/// Counts allocations made by the current thread
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|v| v.set(v.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// Hides `value` from the optimizer, so that the parsing calls cannot be optimized away
+fn black_box<T: Copy>(value: T) -> T {
+    unsafe { std::ptr::read_volatile(&value) }
+}
+
+#[test]
+fn parsing_does_not_allocate() {
+    let headers = [
+        "bytes 0-9/20",
+        "bytes\t 0 \t -\t \t  \t9 / 20   ",
+        "bytes */20",
+        "bytes 0-9/*",
+        "",
+        "foo 1-2/3",
+        "bytes 1-a/3",
+        "bytes 1111111111111111111111111111111111111111111-2/1",
+        "bytes 1-21/20",
+        "bytes 1-3/20 1",
+    ];
+    let options = ParseOptions {
+        range_separator: b"..",
+        allow_thousands_separators: true,
+        ..ParseOptions::default()
+    };
+
+    let before = allocations();
+    for header in headers {
+        black_box(ContentRange::parse(black_box(header)));
+        black_box(ContentRange::parse_bytes(black_box(header.as_bytes())));
+        black_box(ContentRange::parse_ascii(black_box(header.as_bytes())));
+        let _ = black_box(ContentRange::try_parse(black_box(header)));
+        black_box(ContentRange::parse_with_options(
+            black_box(header),
+            &options,
+        ));
+    }
+    assert_eq!(allocations(), before);
+}
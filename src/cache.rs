@@ -0,0 +1,98 @@
+use crate::ContentRange;
+
+/// Parser that remembers the results for the most recently seen headers, see [`CachedParser::parse`].
+///
+/// Useful when the same few distinct values are parsed over and over, e.g. in a proxy.
+/// The cache holds at most `capacity` headers, evicting the least recently used one.
+#[derive(Debug, Clone)]
+pub struct CachedParser {
+    capacity: usize,
+    /// Cached results, from the least to the most recently used
+    entries: Vec<(Box<[u8]>, Option<ContentRange>)>,
+}
+
+impl CachedParser {
+    /// Creates an empty cache holding up to `capacity` headers. A `capacity` of 0 disables caching.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Same as [`ContentRange::parse_bytes`], but returns the cached result if `header`
+    /// was parsed recently. Invalid headers are cached too.
+    ///
+    /// ```
+    /// # use http_content_range::{CachedParser, ContentRange};
+    /// let mut parser = CachedParser::new(16);
+    /// assert_eq!(parser.parse(b"bytes 0-9/20"), ContentRange::parse("bytes 0-9/20"));
+    /// assert_eq!(parser.parse(b"bytes 0-9/20"), ContentRange::parse("bytes 0-9/20"));
+    /// ```
+    pub fn parse(&mut self, header: &[u8]) -> Option<ContentRange> {
+        if let Some(idx) = self.entries.iter().position(|(key, _)| **key == *header) {
+            let entry = self.entries.remove(idx);
+            let value = entry.1;
+            self.entries.push(entry);
+            return value;
+        }
+        let value = ContentRange::parse_bytes(header);
+        if self.capacity > 0 {
+            if self.entries.len() == self.capacity {
+                self.entries.remove(0);
+            }
+            self.entries.push((header.into(), value));
+        }
+        value
+    }
+
+    /// Number of headers currently cached
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if nothing is cached
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached_keys(parser: &CachedParser) -> Vec<&[u8]> {
+        parser.entries.iter().map(|(key, _)| &**key).collect()
+    }
+
+    #[test]
+    fn test_cached_parser() {
+        let mut parser = CachedParser::new(2);
+        assert!(parser.is_empty());
+        for header in [&b"bytes 0-9/20"[..], b"bytes 0-9/*", b"foo", b"bytes */20"] {
+            let expected = ContentRange::parse_bytes(header);
+            assert_eq!(parser.parse(header), expected);
+            assert_eq!(parser.parse(header), expected);
+        }
+        assert_eq!(parser.len(), 2);
+        assert_eq!(cached_keys(&parser), [&b"foo"[..], b"bytes */20"]);
+
+        // a hit makes the entry the most recently used one
+        assert_eq!(parser.parse(b"foo"), None);
+        assert_eq!(
+            parser.parse(b"bytes 0-9/20"),
+            ContentRange::parse("bytes 0-9/20")
+        );
+        assert_eq!(cached_keys(&parser), [&b"foo"[..], b"bytes 0-9/20"]);
+
+        let mut parser = CachedParser::new(0);
+        assert_eq!(
+            parser.parse(b"bytes 0-9/20"),
+            ContentRange::parse("bytes 0-9/20")
+        );
+        assert!(parser.is_empty());
+    }
+}
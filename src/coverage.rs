@@ -0,0 +1,384 @@
+use crate::ContentRangeBytes;
+
+/// Largest number of buckets [`coverage_bitmap`] allocates, plenty for any visualization
+const MAX_BITMAP_BUCKETS: u64 = 1 << 20;
+
+/// Summary of how much of a resource is covered by a set of byte ranges,
+/// see [`multipart_coverage`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Coverage {
+    /// Number of distinct bytes covered by the parts, overlaps are only counted once
+    pub covered_bytes: u64,
+    /// The `complete_length` shared by all parts
+    pub complete_length: u64,
+    /// `true` if the parts form a single run of bytes without gaps
+    pub contiguous: bool,
+}
+
+/// Summarizes the coverage of the parts of a `multipart/byteranges` response.
+///
+/// Returns `None` if there are no parts, if they do not all share the same `complete_length`,
+/// or if any part built by hand breaks the `first_byte <= last_byte < complete_length` invariant.
+///
+/// ```
+/// # use http_content_range::{multipart_coverage, ContentRangeBytes, Coverage};
+/// let parts = [
+///     ContentRangeBytes { first_byte: 0, last_byte: 9, complete_length: 100 },
+///     ContentRangeBytes { first_byte: 10, last_byte: 19, complete_length: 100 },
+/// ];
+/// assert_eq!(multipart_coverage(&parts),
+///     Some(Coverage { covered_bytes: 20, complete_length: 100, contiguous: true }));
+/// ```
+#[must_use]
+pub fn multipart_coverage(parts: &[ContentRangeBytes]) -> Option<Coverage> {
+    let (complete_length, runs) = coalesce(parts)?;
+    Some(Coverage {
+        covered_bytes: runs.iter().map(|(first, last)| last - first + 1).sum(),
+        complete_length,
+        contiguous: runs.len() == 1,
+    })
+}
+
+/// Number of distinct bytes covered by the parts, counting overlapping bytes only once,
+/// unlike summing the length of each part. Same as [`Coverage::covered_bytes`].
+///
+/// Returns `None` if there are no parts, if they do not all share the same `complete_length`,
+/// or if any part built by hand breaks the `first_byte <= last_byte < complete_length` invariant.
+///
+/// ```
+/// # use http_content_range::{total_unique_covered, ContentRangeBytes};
+/// let parts = [
+///     ContentRangeBytes { first_byte: 0, last_byte: 9, complete_length: 100 },
+///     ContentRangeBytes { first_byte: 5, last_byte: 14, complete_length: 100 },
+/// ];
+/// assert_eq!(total_unique_covered(&parts), Some(15));
+/// ```
+#[must_use]
+pub fn total_unique_covered(parts: &[ContentRangeBytes]) -> Option<u64> {
+    multipart_coverage(parts).map(|coverage| coverage.covered_bytes)
+}
+
+/// Lists the ranges of a resource of `complete_length` bytes that are not covered by `have`,
+/// in ascending order, e.g. to plan the requests needed to finish a resumable download.
+///
+/// Returns `None` if any part has a different `complete_length`, i.e. describes some other
+/// version of the resource, or if any part built by hand breaks the
+/// `first_byte <= last_byte < complete_length` invariant.
+///
+/// ```
+/// # use http_content_range::{missing_ranges, ContentRangeBytes};
+/// let have = [ContentRangeBytes { first_byte: 10, last_byte: 19, complete_length: 100 }];
+/// assert_eq!(missing_ranges(100, &have).unwrap(), [
+///     ContentRangeBytes { first_byte: 0, last_byte: 9, complete_length: 100 },
+///     ContentRangeBytes { first_byte: 20, last_byte: 99, complete_length: 100 },
+/// ]);
+/// ```
+#[must_use]
+pub fn missing_ranges(
+    complete_length: u64,
+    have: &[ContentRangeBytes],
+) -> Option<Vec<ContentRangeBytes>> {
+    let runs = if have.is_empty() {
+        Vec::new()
+    } else {
+        match coalesce(have) {
+            Some((length, runs)) if length == complete_length => runs,
+            _ => return None,
+        }
+    };
+    let mut missing = Vec::new();
+    let mut next = 0;
+    for (first, last) in runs {
+        if first > next {
+            missing.push((next, first - 1));
+        }
+        next = last.saturating_add(1);
+    }
+    if next < complete_length {
+        missing.push((next, complete_length - 1));
+    }
+    Some(
+        missing
+            .into_iter()
+            .map(|(first_byte, last_byte)| ContentRangeBytes {
+                first_byte,
+                last_byte,
+                complete_length,
+            })
+            .collect(),
+    )
+}
+
+/// Splits a resource of `complete_length` bytes into buckets of `resolution` bytes,
+/// and marks each bucket that has at least one byte covered by `parts`, e.g. to visualize
+/// the progress of a download. The last bucket may be smaller than `resolution`.
+///
+/// Returns `None` if any part has a different `complete_length`, i.e. describes some other
+/// version of the resource, if `resolution` is 0, or if the bitmap would have more than
+/// 1,048,576 buckets. Parts built by hand that break the
+/// `first_byte <= last_byte < complete_length` invariant are skipped.
+///
+/// ```
+/// # use http_content_range::{coverage_bitmap, ContentRangeBytes};
+/// let parts = [ContentRangeBytes { first_byte: 10, last_byte: 29, complete_length: 100 }];
+/// assert_eq!(coverage_bitmap(&parts, 100, 25).unwrap(), [true, true, false, false]);
+/// ```
+#[must_use]
+pub fn coverage_bitmap(
+    parts: &[ContentRangeBytes],
+    complete_length: u64,
+    resolution: u64,
+) -> Option<Vec<bool>> {
+    if resolution == 0 || parts.iter().any(|p| p.complete_length != complete_length) {
+        return None;
+    }
+    let buckets = complete_length / resolution + u64::from(complete_length % resolution != 0);
+    if buckets > MAX_BITMAP_BUCKETS {
+        return None;
+    }
+    let mut bitmap = vec![false; usize::try_from(buckets).ok()?];
+    for part in parts
+        .iter()
+        .filter(|p| p.first_byte <= p.last_byte && p.last_byte < complete_length)
+    {
+        // both fit, as they are less than `buckets`
+        let first = usize::try_from(part.first_byte / resolution).ok()?;
+        let last = usize::try_from(part.last_byte / resolution).ok()?;
+        bitmap[first..=last]
+            .iter_mut()
+            .for_each(|bucket| *bucket = true);
+    }
+    Some(bitmap)
+}
+
+/// Checks that the ranges form one sequential download: they share the same `complete_length`,
+/// and each range starts right after the previous one ends, without gaps, overlaps, or reordering.
+/// An empty or single-range sequence is trivially contiguous.
+///
+/// ```
+/// # use http_content_range::{validate_contiguous_sequence, ContentRangeBytes};
+/// let ranges = [
+///     ContentRangeBytes { first_byte: 0, last_byte: 9, complete_length: 100 },
+///     ContentRangeBytes { first_byte: 10, last_byte: 19, complete_length: 100 },
+/// ];
+/// assert!(validate_contiguous_sequence(&ranges));
+/// ```
+#[must_use]
+pub fn validate_contiguous_sequence(ranges: &[ContentRangeBytes]) -> bool {
+    ranges.windows(2).all(|pair| {
+        pair[0].complete_length == pair[1].complete_length
+            && pair[0].last_byte.checked_add(1) == Some(pair[1].first_byte)
+    })
+}
+
+/// Sorts the parts and merges the overlapping and adjacent ones into `(first_byte, last_byte)` runs.
+/// Returns `None` if there are no parts, they do not share the same `complete_length`,
+/// or any of them is not a valid range.
+fn coalesce(parts: &[ContentRangeBytes]) -> Option<(u64, Vec<(u64, u64)>)> {
+    let complete_length = parts.first()?.complete_length;
+    if parts.iter().any(|p| {
+        p.complete_length != complete_length
+            || p.first_byte > p.last_byte
+            || p.last_byte >= complete_length
+    }) {
+        return None;
+    }
+    let mut ranges: Vec<_> = parts.iter().map(|p| (p.first_byte, p.last_byte)).collect();
+    ranges.sort_unstable();
+    let mut runs: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (first, last) in ranges {
+        match runs.last_mut() {
+            Some(run) if first <= run.1.saturating_add(1) => run.1 = run.1.max(last),
+            _ => runs.push((first, last)),
+        }
+    }
+    Some((complete_length, runs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(ranges: &[(u64, u64)]) -> Vec<ContentRangeBytes> {
+        ranges
+            .iter()
+            .map(|&(first_byte, last_byte)| ContentRangeBytes {
+                first_byte,
+                last_byte,
+                complete_length: 100,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_multipart_coverage() {
+        let coverage = |covered_bytes, contiguous| {
+            Some(Coverage {
+                covered_bytes,
+                complete_length: 100,
+                contiguous,
+            })
+        };
+        for (ranges, expected) in [
+            (&[(0, 9)][..], coverage(10, true)),
+            (&[(0, 9), (10, 19)], coverage(20, true)),
+            (&[(10, 19), (0, 9), (20, 99)], coverage(100, true)),
+            (&[(0, 9), (5, 14)], coverage(15, true)),
+            (&[(0, 9), (2, 3)], coverage(10, true)),
+            (&[(0, 9), (20, 29)], coverage(20, false)),
+            (&[(50, 59), (0, 9), (5, 14)], coverage(25, false)),
+            (&[], None),
+        ] {
+            assert_eq!(multipart_coverage(&parts(ranges)), expected);
+        }
+
+        let mut mixed = parts(&[(0, 9), (10, 19)]);
+        mixed[1].complete_length = 200;
+        assert_eq!(multipart_coverage(&mixed), None);
+
+        // parts built by hand, bypassing validation
+        for ranges in [&[(0, 9), (20, 10)][..], &[(0, 100)], &[(50, u64::MAX)]] {
+            assert_eq!(multipart_coverage(&parts(ranges)), None, "{ranges:?}");
+        }
+    }
+
+    #[test]
+    fn test_total_unique_covered() {
+        for (ranges, naive_sum, expected) in [
+            (&[(0, 9), (20, 29)][..], 20, Some(20)),
+            (&[(0, 9), (5, 14)], 20, Some(15)),
+            (&[(0, 99), (10, 19), (50, 59)], 120, Some(100)),
+            (&[(10, 19), (10, 19)], 20, Some(10)),
+            (&[], 0, None),
+        ] {
+            let parts = parts(ranges);
+            let sum: u64 = parts.iter().map(|p| p.last_byte - p.first_byte + 1).sum();
+            assert_eq!(sum, naive_sum);
+            assert_eq!(total_unique_covered(&parts), expected);
+        }
+
+        let mut mixed = parts(&[(0, 9), (10, 19)]);
+        mixed[1].complete_length = 200;
+        assert_eq!(total_unique_covered(&mixed), None);
+
+        // parts built by hand, bypassing validation
+        for ranges in [&[(20, 10)][..], &[(0, 9), (0, 100)], &[(50, u64::MAX)]] {
+            assert_eq!(total_unique_covered(&parts(ranges)), None, "{ranges:?}");
+        }
+    }
+
+    #[test]
+    fn test_missing_ranges() {
+        for (ranges, expected) in [
+            (&[][..], &[(0, 99)][..]),
+            (&[(0, 99)], &[]),
+            (&[(0, 49), (50, 99)], &[]),
+            (&[(0, 9), (20, 99)], &[(10, 19)]),
+            (&[(10, 99)], &[(0, 9)]),
+            (&[(0, 89)], &[(90, 99)]),
+            (&[(10, 19), (50, 59)], &[(0, 9), (20, 49), (60, 99)]),
+            (&[(30, 59), (10, 39), (15, 20)], &[(0, 9), (60, 99)]),
+        ] {
+            assert_eq!(missing_ranges(100, &parts(ranges)), Some(parts(expected)));
+        }
+
+        let mut mixed = parts(&[(0, 9), (10, 19)]);
+        mixed[1].complete_length = 200;
+        assert_eq!(missing_ranges(100, &mixed), None);
+        assert_eq!(missing_ranges(200, &mixed), None);
+        assert_eq!(missing_ranges(200, &parts(&[(0, 9)])), None);
+        assert_eq!(missing_ranges(0, &[]), Some(vec![]));
+
+        // parts built by hand, bypassing validation
+        for ranges in [&[(20, 10)][..], &[(0, 9), (20, 10)], &[(90, 100)]] {
+            assert_eq!(missing_ranges(100, &parts(ranges)), None, "{ranges:?}");
+        }
+    }
+
+    #[test]
+    fn test_coverage_bitmap() {
+        for (ranges, resolution, expected) in [
+            (&[(0, 99)][..], 10, &[true; 10][..]),
+            (&[(0, 49), (50, 99)], 25, &[true; 4]),
+            (&[], 25, &[false; 4]),
+            (&[(10, 29)], 25, &[true, true, false, false]),
+            (&[(24, 24)], 25, &[true, false, false, false]),
+            (&[(25, 25)], 25, &[false, true, false, false]),
+            (&[(24, 25)], 25, &[true, true, false, false]),
+            (&[(99, 99)], 30, &[false, false, false, true]),
+            (
+                &[(0, 0), (99, 99)],
+                1,
+                &[&[true][..], &[false; 98], &[true]].concat(),
+            ),
+            (&[(40, 59)], 100, &[true]),
+            (&[(40, 59)], 1000, &[true]),
+        ] {
+            assert_eq!(
+                coverage_bitmap(&parts(ranges), 100, resolution).as_deref(),
+                Some(expected),
+                "{ranges:?} {resolution}"
+            );
+        }
+
+        let mut mixed = parts(&[(0, 9), (90, 99)]);
+        mixed[1].complete_length = 200;
+        assert_eq!(coverage_bitmap(&mixed, 100, 50), None);
+        assert_eq!(coverage_bitmap(&mixed, 200, 50), None);
+        assert_eq!(coverage_bitmap(&parts(&[(0, 9)]), 100, 0), None);
+        assert_eq!(coverage_bitmap(&[], 0, 10), Some(Vec::new()));
+
+        // parts built by hand, bypassing validation, are skipped
+        let invalid = parts(&[
+            (0, 9),
+            (20, 10),
+            (50, 150),
+            (100, 100),
+            (u64::MAX, u64::MAX),
+        ]);
+        assert_eq!(
+            coverage_bitmap(&invalid, 100, 25),
+            Some(vec![true, false, false, false])
+        );
+
+        // the allocation is limited
+        let huge = [ContentRangeBytes {
+            first_byte: 0,
+            last_byte: 9,
+            complete_length: u64::MAX,
+        }];
+        assert_eq!(coverage_bitmap(&huge, u64::MAX, 1), None);
+        assert_eq!(
+            coverage_bitmap(&[], 1 << 20, 1).map(|v| v.len()),
+            Some(1 << 20)
+        );
+        assert_eq!(coverage_bitmap(&[], (1 << 20) + 1, 1), None);
+        assert_eq!(
+            coverage_bitmap(&huge, u64::MAX, 1 << 44).map(|v| v.len()),
+            Some(1 << 20)
+        );
+    }
+
+    #[test]
+    fn test_validate_contiguous_sequence() {
+        for (ranges, expected) in [
+            (&[][..], true),
+            (&[(10, 19)], true),
+            (&[(0, 9), (10, 19), (20, 99)], true),
+            (&[(0, 9), (11, 19)], false),
+            (&[(0, 9), (9, 19)], false),
+            (&[(10, 19), (0, 9)], false),
+            (&[(0, 9), (20, 29), (10, 19)], false),
+        ] {
+            assert_eq!(
+                validate_contiguous_sequence(&parts(ranges)),
+                expected,
+                "{ranges:?}"
+            );
+        }
+
+        let mut mixed = parts(&[(0, 9), (10, 19)]);
+        mixed[1].complete_length = 200;
+        assert!(!validate_contiguous_sequence(&mixed));
+    }
+}
@@ -0,0 +1,118 @@
+use crate::ContentRange;
+
+/// Differences between two [`ContentRange`] values, see [`ContentRange::diff`].
+///
+/// Each numeric field holds the `(self, other)` pair when both values have that field
+/// and it differs. A field that only one of the values has is reported via `variant`.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct RangeDiff {
+    /// `true` if the values are different variants
+    pub variant: bool,
+    /// Differing `first_byte` values
+    pub first_byte: Option<(u64, u64)>,
+    /// Differing `last_byte` values
+    pub last_byte: Option<(u64, u64)>,
+    /// Differing `complete_length` values
+    pub complete_length: Option<(u64, u64)>,
+}
+
+impl RangeDiff {
+    /// Returns `true` if no differences were found, i.e. the values are equal.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl ContentRange {
+    /// Describes how this value differs from `other`, e.g. to compare responses
+    /// of two servers to the same request.
+    ///
+    /// ```
+    /// # use http_content_range::{ContentRange, RangeDiff};
+    /// let a = ContentRange::parse("bytes 0-9/20").unwrap();
+    /// let b = ContentRange::parse("bytes 0-9/30").unwrap();
+    /// assert!(a.diff(&a).is_empty());
+    /// assert_eq!(a.diff(&b), RangeDiff { complete_length: Some((20, 30)), ..RangeDiff::default() });
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &ContentRange) -> RangeDiff {
+        fn pair(left: Option<u64>, right: Option<u64>) -> Option<(u64, u64)> {
+            match (left, right) {
+                (Some(left), Some(right)) if left != right => Some((left, right)),
+                _ => None,
+            }
+        }
+        let (bounds, other_bounds) = (self.bounds(), other.bounds());
+        RangeDiff {
+            variant: std::mem::discriminant(self) != std::mem::discriminant(other),
+            first_byte: pair(bounds.map(|b| b.0), other_bounds.map(|b| b.0)),
+            last_byte: pair(bounds.map(|b| b.1), other_bounds.map(|b| b.1)),
+            complete_length: pair(self.complete_length(), other.complete_length()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(left: &str, right: &str) -> RangeDiff {
+        let left = ContentRange::parse(left).unwrap();
+        let right = ContentRange::parse(right).unwrap();
+        left.diff(&right)
+    }
+
+    #[test]
+    fn test_diff() {
+        for header in ["bytes 0-9/20", "bytes 0-9/*", "bytes */20"] {
+            assert!(diff(header, header).is_empty());
+        }
+        let none = RangeDiff::default();
+        for (left, right, expected) in [
+            (
+                "bytes 0-9/20",
+                "bytes 1-9/20",
+                RangeDiff {
+                    first_byte: Some((0, 1)),
+                    ..none
+                },
+            ),
+            (
+                "bytes 0-9/*",
+                "bytes 0-8/*",
+                RangeDiff {
+                    last_byte: Some((9, 8)),
+                    ..none
+                },
+            ),
+            (
+                "bytes */20",
+                "bytes */30",
+                RangeDiff {
+                    complete_length: Some((20, 30)),
+                    ..none
+                },
+            ),
+            (
+                "bytes 0-9/20",
+                "bytes 0-9/*",
+                RangeDiff {
+                    variant: true,
+                    ..none
+                },
+            ),
+            (
+                "bytes 0-9/20",
+                "bytes */30",
+                RangeDiff {
+                    variant: true,
+                    complete_length: Some((20, 30)),
+                    ..none
+                },
+            ),
+        ] {
+            assert_eq!(diff(left, right), expected);
+        }
+    }
+}
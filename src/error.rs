@@ -0,0 +1,229 @@
+use std::fmt;
+
+/// Number of input bytes shown on each side of the failure offset by [`ParseError::with_context`]
+const CONTEXT_RADIUS: usize = 4;
+
+/// Numeric field of a Content-Range value.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Field {
+    /// First byte position of a byte range
+    FirstByte,
+    /// Last byte position of a byte range
+    LastByte,
+    /// Complete length of the resource
+    CompleteLength,
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Field::FirstByte => "first byte",
+            Field::LastByte => "last byte",
+            Field::CompleteLength => "complete length",
+        })
+    }
+}
+
+/// Reason why a numeric field is not a valid number, the [`source`](std::error::Error::source)
+/// of [`ParseError::InvalidNumber`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum NumberError {
+    /// The field is empty
+    Empty,
+    /// The field contains a character other than a decimal digit
+    InvalidDigit,
+    /// The number does not fit into `u64`
+    Overflow,
+}
+
+impl fmt::Display for NumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            NumberError::Empty => "cannot parse number from empty string",
+            NumberError::InvalidDigit => "invalid digit found in string",
+            NumberError::Overflow => "number too large to fit in u64",
+        })
+    }
+}
+
+impl std::error::Error for NumberError {}
+
+/// Reason why a Content-Range value could not be parsed or constructed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// `first_byte` is greater than `last_byte`
+    FirstAfterLast,
+    /// `last_byte` is not less than `complete_length`
+    LastBeyondLength,
+    /// The value does not start with the `bytes` unit
+    UnknownUnit,
+    /// The value ended before it was complete
+    UnexpectedEnd,
+    /// The byte at `offset` is not allowed at this position
+    UnexpectedByte { offset: usize },
+    /// A `*` at `offset` is in place of a number, e.g. `bytes *-9/20`. A `*` is only
+    /// allowed as the whole range (`bytes */20`) or as the complete length (`bytes 0-9/*`)
+    UnexpectedStar { offset: usize },
+    /// A number was expected at `offset`
+    ExpectedNumber { offset: usize },
+    /// The number starting at `offset` does not fit into `u64`
+    NumberOverflow { offset: usize },
+    /// The given field is not a valid `u64` decimal number
+    InvalidNumber { field: Field, error: NumberError },
+    /// The given field has more `digits` than any `u64` value in the configured radix
+    /// (20 in decimal), which is likely a bug rather than a legitimately large value
+    NumberTooLong { field: Field, digits: usize },
+    /// The number starting at `offset` exceeds [`ParseOptions::max_value`](crate::ParseOptions::max_value)
+    NumberAboveMax { offset: usize },
+    /// The given field is written as a hexadecimal number, e.g. `0x14`, only decimal is allowed
+    HexNotAllowed { field: Field },
+    /// The given field is required, but was not provided
+    MissingField { field: Field },
+    /// The given field was provided, but is not used by this kind of range
+    UnexpectedField { field: Field },
+    /// [`ParseOptions::radix`](crate::ParseOptions::radix) is not between 2 and 16
+    UnsupportedRadix { radix: u32 },
+}
+
+impl ParseError {
+    /// Position within the input where parsing failed, if it points to a specific byte.
+    #[must_use]
+    pub fn offset(&self) -> Option<usize> {
+        match *self {
+            ParseError::UnexpectedByte { offset }
+            | ParseError::UnexpectedStar { offset }
+            | ParseError::ExpectedNumber { offset }
+            | ParseError::NumberOverflow { offset }
+            | ParseError::NumberAboveMax { offset } => Some(offset),
+            ParseError::FirstAfterLast
+            | ParseError::LastBeyondLength
+            | ParseError::UnknownUnit
+            | ParseError::UnexpectedEnd
+            | ParseError::InvalidNumber { .. }
+            | ParseError::NumberTooLong { .. }
+            | ParseError::HexNotAllowed { .. }
+            | ParseError::MissingField { .. }
+            | ParseError::UnexpectedField { .. }
+            | ParseError::UnsupportedRadix { .. } => None,
+        }
+    }
+
+    /// Stable short code identifying the kind of error, e.g. `first_gt_last`, for logs or FFI.
+    /// Codes never change once published, while the [`Display`](fmt::Display) message may.
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseError::FirstAfterLast => "first_gt_last",
+            ParseError::LastBeyondLength => "last_ge_length",
+            ParseError::UnknownUnit => "no_prefix",
+            ParseError::UnexpectedEnd => "unexpected_end",
+            ParseError::UnexpectedByte { .. } => "unexpected_byte",
+            ParseError::UnexpectedStar { .. } => "unexpected_star",
+            ParseError::ExpectedNumber { .. } | ParseError::InvalidNumber { .. } => "bad_number",
+            ParseError::NumberOverflow { .. } => "number_overflow",
+            ParseError::NumberTooLong { .. } => "number_too_long",
+            ParseError::NumberAboveMax { .. } => "number_above_max",
+            ParseError::HexNotAllowed { .. } => "hex_number",
+            ParseError::MissingField { .. } => "missing_field",
+            ParseError::UnexpectedField { .. } => "unexpected_field",
+            ParseError::UnsupportedRadix { .. } => "unsupported_radix",
+        }
+    }
+
+    /// Attaches a snippet of `header` surrounding the failure offset to this error.
+    /// Errors without an [`offset`](Self::offset) show the end of the input.
+    /// `header` must be the input that produced this error.
+    #[must_use]
+    pub fn with_context(self, header: &[u8]) -> ParseErrorWithContext {
+        let offset = self.offset().unwrap_or(header.len()).min(header.len());
+        let start = offset.saturating_sub(CONTEXT_RADIUS);
+        let end = offset.saturating_add(CONTEXT_RADIUS).min(header.len());
+        ParseErrorWithContext {
+            error: self,
+            context: String::from_utf8_lossy(&header[start..end]).into_owned(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::FirstAfterLast => f.write_str("first byte is greater than last byte"),
+            ParseError::LastBeyondLength => {
+                f.write_str("last byte is not less than the complete length")
+            }
+            ParseError::UnknownUnit => f.write_str("range unit is not `bytes`"),
+            ParseError::UnexpectedEnd => f.write_str("unexpected end of input"),
+            ParseError::UnexpectedByte { offset } => {
+                write!(f, "unexpected character at offset {offset}")
+            }
+            ParseError::UnexpectedStar { offset } => {
+                write!(f, "unexpected `*` instead of a number at offset {offset}")
+            }
+            ParseError::ExpectedNumber { offset } => {
+                write!(f, "expected a number at offset {offset}")
+            }
+            ParseError::NumberOverflow { offset } => {
+                write!(f, "number at offset {offset} is too large")
+            }
+            ParseError::InvalidNumber { field, .. } => {
+                write!(f, "{field} is not a valid number")
+            }
+            ParseError::NumberTooLong { field, digits } => {
+                write!(
+                    f,
+                    "{field} has {digits} digits, more than any 64-bit number"
+                )
+            }
+            ParseError::NumberAboveMax { offset } => {
+                write!(
+                    f,
+                    "number at offset {offset} exceeds the configured maximum"
+                )
+            }
+            ParseError::HexNotAllowed { field } => {
+                write!(f, "{field} must be a decimal number, not hexadecimal")
+            }
+            ParseError::MissingField { field } => write!(f, "{field} is missing"),
+            ParseError::UnexpectedField { field } => {
+                write!(f, "{field} is not expected for this kind of range")
+            }
+            ParseError::UnsupportedRadix { radix } => {
+                write!(f, "radix {radix} is not supported, only 2 to 16")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::InvalidNumber { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// A [`ParseError`] together with a short snippet of the input around the failure,
+/// see [`ContentRange::try_parse_verbose`](crate::ContentRange::try_parse_verbose).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseErrorWithContext {
+    /// The underlying parse error
+    pub error: ParseError,
+    /// Input bytes surrounding the failure, lossily converted to UTF-8
+    pub context: String,
+}
+
+impl fmt::Display for ParseErrorWithContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} near `{}`", self.error, self.context)
+    }
+}
+
+impl std::error::Error for ParseErrorWithContext {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
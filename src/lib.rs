@@ -1,15 +1,42 @@
 #![doc = include_str!("../README.md")]
 
+use std::borrow::Cow;
+use std::io::{BufRead, Read, Write};
+use std::ops::{Range, RangeInclusive};
 use std::str::FromStr;
+use std::{fmt, io};
 
-use crate::utils::{fail_if, is_whitespace, IterExt};
+#[cfg(feature = "cache")]
+pub use crate::cache::CachedParser;
+pub use crate::coverage::{
+    coverage_bitmap, missing_ranges, multipart_coverage, total_unique_covered,
+    validate_contiguous_sequence, Coverage,
+};
+pub use crate::diff::RangeDiff;
+pub use crate::error::{Field, NumberError, ParseError, ParseErrorWithContext};
+pub use crate::options::ParseOptions;
+use crate::utils::{fail_if, is_token, is_whitespace, parse_u64_exact, Cursor};
 
+#[cfg(feature = "cache")]
+mod cache;
+mod coverage;
+mod diff;
+mod error;
+mod options;
 mod utils;
 
 const PREFIX: &[u8] = b"bytes";
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+/// Longest header accepted by [`ContentRange::parse_dos_safe`]. The longest canonical value
+/// is 68 bytes, leaving some room for extra whitespace.
+const DOS_SAFE_MAX_LEN: usize = 128;
+/// Name of the Content-Range header, as commonly written in HTTP/1.1.
+pub const CONTENT_RANGE: &str = "Content-Range";
+/// Name of the Content-Range header in lowercase, as required by HTTP/2 and HTTP/3.
+pub const CONTENT_RANGE_LOWER: &str = "content-range";
 
 /// HTTP Content-Range response header representation.
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq)]
 pub enum ContentRange {
     /// Regular bytes range response with status 206
     Bytes(ContentRangeBytes),
@@ -32,6 +59,75 @@ pub struct ContentRangeUnbound {
     pub last_byte: u64,
 }
 
+/// Unified view of the two byte range variants, see [`ContentRange::byte_view`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ByteView {
+    pub first_byte: u64,
+    pub last_byte: u64,
+    /// `None` if the complete length is unknown (`*`)
+    pub complete_length: Option<u64>,
+}
+
+/// Variant of a [`ContentRange`] without its data, see [`ContentRange::from_kind_and_parts`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ContentRangeKind {
+    /// [`ContentRange::Bytes`], e.g. `bytes 42-69/420`
+    Bytes,
+    /// [`ContentRange::UnboundBytes`], e.g. `bytes 42-69/*`
+    UnboundBytes,
+    /// [`ContentRange::Unsatisfied`], e.g. `bytes */420`
+    Unsatisfied,
+}
+
+/// Range unit of a Content-Range header, see [`ContentRange::detected_unit`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DetectedUnit<'a> {
+    /// The `bytes` unit, the only one this crate can parse
+    Bytes,
+    /// Any other unit, a valid RFC 7230 `token`, e.g. `my-unit.v2`
+    Other(&'a str),
+    /// The header does not start with a `token SP` prefix
+    None,
+}
+
+/// Positions of the tokens of a parsed Content-Range header, as byte ranges within the input.
+/// See [`ContentRange::parse_spans`].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct ContentRangeSpans {
+    /// The range unit, e.g. `bytes`
+    pub unit: Range<usize>,
+    /// The first byte position, absent for unsatisfied ranges
+    pub first_byte: Option<Range<usize>>,
+    /// The last byte position, absent for unsatisfied ranges
+    pub last_byte: Option<Range<usize>>,
+    /// The complete length, absent if it is unknown (`*`)
+    pub complete_length: Option<Range<usize>>,
+}
+
+/// Parsed value bundled with the input it was parsed from, see [`ContentRange::parse_retaining`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ParsedRange<'a> {
+    /// The parsed value, `None` if the input is not valid
+    pub value: Option<ContentRange>,
+    /// The original input
+    pub source: &'a str,
+}
+
+/// Result of parsing a possibly truncated header, see [`ContentRange::parse_maybe_incomplete`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ParseProgress {
+    /// The header is a valid value
+    Complete(ContentRange),
+    /// The header ended early, but more input could still make it valid
+    Incomplete,
+    /// The header is not valid, regardless of any further input
+    Invalid,
+}
+
+/// Unsatisfied range response, e.g. `bytes */420`.
+///
+/// A `complete_length` of `0` (`bytes */0`) describes an empty resource,
+/// for which every range request is unsatisfiable.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct ContentRangeUnsatisfied {
     pub complete_length: u64,
@@ -61,6 +157,161 @@ impl FromStr for ContentRange {
     }
 }
 
+impl Default for ContentRangeBytes {
+    /// The smallest valid byte range, `bytes 0-0/1`
+    fn default() -> Self {
+        Self {
+            first_byte: 0,
+            last_byte: 0,
+            complete_length: 1,
+        }
+    }
+}
+
+impl Default for ContentRangeUnbound {
+    /// The smallest valid unbound byte range, `bytes 0-0/*`
+    fn default() -> Self {
+        Self {
+            first_byte: 0,
+            last_byte: 0,
+        }
+    }
+}
+
+impl Default for ContentRangeUnsatisfied {
+    /// An empty resource, `bytes */0`
+    fn default() -> Self {
+        Self { complete_length: 0 }
+    }
+}
+
+impl TryFrom<(u64, u64, u64)> for ContentRangeBytes {
+    type Error = ParseError;
+
+    /// Creates a byte range from a `(first_byte, last_byte, complete_length)` tuple,
+    /// ensuring that `first_byte <= last_byte < complete_length`.
+    fn try_from(
+        (first_byte, last_byte, complete_length): (u64, u64, u64),
+    ) -> Result<Self, Self::Error> {
+        ContentRangeUnbound::try_from((first_byte, last_byte))?;
+        if last_byte >= complete_length {
+            return Err(ParseError::LastBeyondLength);
+        }
+        Ok(Self {
+            first_byte,
+            last_byte,
+            complete_length,
+        })
+    }
+}
+
+impl TryFrom<(u64, u64)> for ContentRangeUnbound {
+    type Error = ParseError;
+
+    /// Creates an unbound byte range from a `(first_byte, last_byte)` tuple,
+    /// ensuring that `first_byte <= last_byte`.
+    fn try_from((first_byte, last_byte): (u64, u64)) -> Result<Self, Self::Error> {
+        if first_byte > last_byte {
+            return Err(ParseError::FirstAfterLast);
+        }
+        Ok(Self {
+            first_byte,
+            last_byte,
+        })
+    }
+}
+
+impl From<ContentRangeBytes> for RangeInclusive<u64> {
+    /// Converts to `first_byte..=last_byte`
+    fn from(value: ContentRangeBytes) -> Self {
+        value.first_byte..=value.last_byte
+    }
+}
+
+impl From<ContentRangeUnbound> for RangeInclusive<u64> {
+    /// Converts to `first_byte..=last_byte`
+    fn from(value: ContentRangeUnbound) -> Self {
+        value.first_byte..=value.last_byte
+    }
+}
+
+impl fmt::Debug for ContentRange {
+    /// The regular `{:?}` output matches the derived one.
+    /// The alternate `{:#?}` output is compact and guaranteed to stay stable across versions,
+    /// e.g. `ContentRange::Bytes { 42..=69 / 420 }`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            match self {
+                ContentRange::Bytes(r) => write!(
+                    f,
+                    "ContentRange::Bytes {{ {}..={} / {} }}",
+                    r.first_byte, r.last_byte, r.complete_length
+                ),
+                ContentRange::UnboundBytes(r) => write!(
+                    f,
+                    "ContentRange::UnboundBytes {{ {}..={} / * }}",
+                    r.first_byte, r.last_byte
+                ),
+                ContentRange::Unsatisfied(r) => write!(
+                    f,
+                    "ContentRange::Unsatisfied {{ * / {} }}",
+                    r.complete_length
+                ),
+            }
+        } else {
+            match self {
+                ContentRange::Bytes(r) => f.debug_tuple("Bytes").field(r).finish(),
+                ContentRange::UnboundBytes(r) => f.debug_tuple("UnboundBytes").field(r).finish(),
+                ContentRange::Unsatisfied(r) => f.debug_tuple("Unsatisfied").field(r).finish(),
+            }
+        }
+    }
+}
+
+impl fmt::Display for ContentRange {
+    /// Formats the value as a canonical Content-Range header value, e.g. `bytes 42-69/420`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContentRange::Bytes(r) => r.fmt(f),
+            ContentRange::UnboundBytes(r) => r.fmt(f),
+            ContentRange::Unsatisfied(r) => r.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for ContentRangeBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bytes {}-{}/{}",
+            self.first_byte, self.last_byte, self.complete_length
+        )
+    }
+}
+
+impl fmt::Display for ContentRangeUnbound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bytes {}-{}/*", self.first_byte, self.last_byte)
+    }
+}
+
+impl fmt::Display for ContentRangeUnsatisfied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bytes */{}", self.complete_length)
+    }
+}
+
+impl fmt::Display for ParsedRange<'_> {
+    /// Formats the canonical value together with the original input,
+    /// e.g. ``bytes 0-9/20 (parsed from `bytes 0 - 9/20`)``.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{value} (parsed from `{}`)", self.source),
+            None => write!(f, "invalid Content-Range `{}`", self.source),
+        }
+    }
+}
+
 impl ContentRange {
     /// Parses Content-Range HTTP header string as per
     /// [RFC 7233](https://httpwg.org/specs/rfc7233.html#header.content-range).
@@ -70,6 +321,8 @@ impl ContentRange {
     /// This parser is a bit more lenient than the official RFC, it allows spaces and tabs between everything.
     /// See <https://httpwg.org/specs/rfc7233.html#rfc.section.4.2>
     ///
+    /// Parsing never allocates on the heap.
+    ///
     /// ```
     /// # use http_content_range::{ContentRange, ContentRangeBytes, ContentRangeUnbound, ContentRangeUnsatisfied};
     /// assert_eq!(ContentRange::parse("bytes 42-69/420").unwrap(),
@@ -89,6 +342,163 @@ impl ContentRange {
         Self::parse_bytes(header.as_bytes())
     }
 
+    /// Same as [`parse`](Self::parse), but accepts anything convertible into a `Cow<str>`,
+    /// e.g. `&str`, `String`, or `Cow<str>` itself.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// # use std::borrow::Cow;
+    /// let header: Cow<str> = Cow::Owned(String::from("bytes 0-9/20"));
+    /// assert!(ContentRange::parse_cow(header).is_some());
+    /// ```
+    #[must_use]
+    pub fn parse_cow<'a>(header: impl Into<Cow<'a, str>>) -> Option<ContentRange> {
+        Self::parse(&header.into())
+    }
+
+    /// Same as [`parse_bytes`](Self::parse_bytes), but rejects the header up front
+    /// if it contains any non-ASCII byte (`>= 0x80`), regardless of its position.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// assert!(ContentRange::parse_ascii(b"bytes 42-69/420").is_some());
+    /// assert!(ContentRange::parse_ascii(b"bytes 42-69/420\xFF").is_none());
+    /// ```
+    #[must_use]
+    pub fn parse_ascii(header: &[u8]) -> Option<ContentRange> {
+        fail_if(!header.is_ascii())?;
+        Self::parse_bytes(header)
+    }
+
+    /// Same as [`parse`](Self::parse), but keeps the input together with the result,
+    /// e.g. for logging.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// let parsed = ContentRange::parse_retaining("bytes 0 - 9/20");
+    /// assert_eq!(parsed.to_string(), "bytes 0-9/20 (parsed from `bytes 0 - 9/20`)");
+    /// ```
+    #[must_use]
+    pub fn parse_retaining(header: &str) -> ParsedRange<'_> {
+        ParsedRange {
+            value: Self::parse(header),
+            source: header,
+        }
+    }
+
+    /// Same as [`parse`](Self::parse), but tolerates a non-standard trailing `;` parameter,
+    /// e.g. `bytes 0-9/20;foo=bar`. The text after the `;` is returned as is, without interpreting it.
+    ///
+    /// ```
+    /// # use http_content_range::{ContentRange, ContentRangeBytes};
+    /// let (value, params) = ContentRange::parse_with_params("bytes 0-9/20;foo=bar");
+    /// assert_eq!(value.unwrap(),
+    ///     ContentRange::Bytes(ContentRangeBytes{first_byte: 0, last_byte: 9, complete_length: 20}));
+    /// assert_eq!(params, Some("foo=bar"));
+    /// ```
+    #[must_use]
+    pub fn parse_with_params(header: &str) -> (Option<ContentRange>, Option<&str>) {
+        match header.split_once(';') {
+            Some((range, params)) => (Self::parse(range), Some(params)),
+            None => (Self::parse(header), None),
+        }
+    }
+
+    /// Detects the range unit of the header without parsing the rest of it,
+    /// e.g. to route headers with different units.
+    ///
+    /// ```
+    /// # use http_content_range::{ContentRange, DetectedUnit};
+    /// assert_eq!(ContentRange::detected_unit("bytes 0-9/20"), DetectedUnit::Bytes);
+    /// assert_eq!(ContentRange::detected_unit("items 0-9/20"), DetectedUnit::Other("items"));
+    /// assert_eq!(ContentRange::detected_unit("0-9/20"), DetectedUnit::None);
+    /// ```
+    #[must_use]
+    pub fn detected_unit(header: &str) -> DetectedUnit<'_> {
+        match header.split_once([' ', '\t']) {
+            Some((unit, _)) if unit.as_bytes() == PREFIX => DetectedUnit::Bytes,
+            Some((unit, _)) if is_token(unit.as_bytes()) => DetectedUnit::Other(unit),
+            Some(_) | None => DetectedUnit::None,
+        }
+    }
+
+    /// Same as [`parse`](Self::parse), but also returns the range unit token of the header.
+    /// Headers with units other than `bytes` cannot be parsed, but their unit is still reported.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// assert_eq!(ContentRange::parse_detailed("bytes 0-9/20").1, Some("bytes"));
+    /// assert_eq!(ContentRange::parse_detailed("items 0-9/20"), (None, Some("items")));
+    /// ```
+    #[must_use]
+    pub fn parse_detailed(header: &str) -> (Option<ContentRange>, Option<&str>) {
+        let unit = match Self::detected_unit(header) {
+            DetectedUnit::Bytes => Some(&header[..PREFIX.len()]),
+            DetectedUnit::Other(unit) => Some(unit),
+            DetectedUnit::None => None,
+        };
+        (Self::parse(header), unit)
+    }
+
+    /// Reads a header value from `reader` up to the first newline, and parses it.
+    ///
+    /// At most `max` bytes are consumed, guarding against unbounded input, so the value must end
+    /// with a newline or the end of input within that limit. Nothing past the newline is consumed.
+    /// The newline and a preceding `\r` are not part of the parsed value.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// # use std::io::Cursor;
+    /// let mut reader = Cursor::new(&b"bytes 0-9/20\r\nnext line"[..]);
+    /// assert!(ContentRange::parse_from_reader(&mut reader, 100).unwrap().is_some());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns any I/O error produced by `reader`, or an [`io::ErrorKind::InvalidData`] error
+    /// if `max` bytes were read without reaching a newline or the end of input.
+    pub fn parse_from_reader<R: BufRead>(
+        reader: &mut R,
+        max: usize,
+    ) -> io::Result<Option<ContentRange>> {
+        let mut buf = Vec::new();
+        reader
+            .by_ref()
+            .take(u64::try_from(max).unwrap_or(u64::MAX))
+            .read_until(b'\n', &mut buf)?;
+        if buf.last() == Some(&b'\n') {
+            buf.pop();
+        } else if buf.len() == max && !reader.fill_buf()?.is_empty() {
+            // a value cut off at the limit must not be parsed as if it was complete
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Content-Range value is longer than the limit",
+            ));
+        }
+        if buf.last() == Some(&b'\r') {
+            buf.pop();
+        }
+        Ok(Self::parse_bytes(&buf))
+    }
+
+    /// Parses a complete `Content-Range: <value>` header line.
+    ///
+    /// The field name is matched case-insensitively, so both [`CONTENT_RANGE`] and the HTTP/2
+    /// [`CONTENT_RANGE_LOWER`] forms are accepted. The whitespace around the value is ignored.
+    /// Returns `None` if the line has no `:` or is for a different header.
+    ///
+    /// ```
+    /// # use http_content_range::{ContentRange, ContentRangeBytes};
+    /// assert_eq!(ContentRange::parse_header_line("Content-Range: bytes 0-9/20").unwrap(),
+    ///     ContentRange::Bytes(ContentRangeBytes{first_byte: 0, last_byte: 9, complete_length: 20}));
+    /// assert!(ContentRange::parse_header_line("Content-Length: 20").is_none());
+    /// ```
+    #[must_use]
+    pub fn parse_header_line(line: &str) -> Option<ContentRange> {
+        let (name, value) = line.split_once(':')?;
+        fail_if(!name.eq_ignore_ascii_case(CONTENT_RANGE))?;
+        Self::parse(value.trim_matches([' ', '\t']))
+    }
+
     /// From <https://httpwg.org/specs/rfc7233.html#rfc.section.4.2>
     /// Valid bytes responses:
     ///   Content-Range: bytes 42-1233/1234
@@ -114,37 +524,334 @@ impl ContentRange {
     /// Same as [`parse`](Self::parse) but parses directly from the byte array
     #[must_use]
     pub fn parse_bytes(header: &[u8]) -> Option<ContentRange> {
-        if !header.starts_with(PREFIX) {
-            return None;
+        Self::parse_bytes_with_options(header, &ParseOptions::default())
+    }
+
+    /// Parses a value at the start of `input`, and advances `input` past it, leaving
+    /// any following bytes to the caller, e.g. in a streaming decoder.
+    /// On failure, returns `None` and leaves `input` unchanged.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// let mut input = &b"bytes 0-9/20\r\nrest"[..];
+    /// assert_eq!(ContentRange::parse_advancing(&mut input), ContentRange::parse("bytes 0-9/20"));
+    /// assert_eq!(input, b"\r\nrest");
+    /// ```
+    #[must_use]
+    pub fn parse_advancing(input: &mut &[u8]) -> Option<ContentRange> {
+        let mut spans = ContentRangeSpans::default();
+        let (res, end) =
+            Self::try_parse_prefix(input, &ParseOptions::default(), &mut spans).ok()?;
+        *input = &input[end..];
+        Some(res)
+    }
+
+    /// Same as [`parse_bytes`](Self::parse_bytes), but with limits suitable for untrusted input:
+    /// the header may be at most 128 bytes long, each numeric field at most 20 digits
+    /// including leading zeros, and trailing whitespace at most 16 bytes.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// assert!(ContentRange::parse_dos_safe(b"bytes 0-9/20").is_some());
+    /// assert!(ContentRange::parse_dos_safe(b"bytes 000000000000000000000-9/20").is_none());
+    /// ```
+    #[must_use]
+    pub fn parse_dos_safe(header: &[u8]) -> Option<ContentRange> {
+        fail_if(header.len() > DOS_SAFE_MAX_LEN)?;
+        let options = ParseOptions {
+            max_digits: Some(20),
+            max_trailing_whitespace: Some(16),
+            ..ParseOptions::default()
+        };
+        Self::parse_bytes_with_options(header, &options)
+    }
+
+    /// Same as [`parse_bytes`](Self::parse_bytes), but tells apart a header that was cut short,
+    /// e.g. while it is still being received, from an invalid one.
+    ///
+    /// A header ending in the middle of a number is [`Incomplete`](ParseProgress::Incomplete)
+    /// only if it is not valid yet. Once it is valid, it is [`Complete`](ParseProgress::Complete)
+    /// even if more digits could follow, so the end of the header must still be found separately.
+    ///
+    /// ```
+    /// # use http_content_range::{ContentRange, ParseProgress};
+    /// assert_eq!(ContentRange::parse_maybe_incomplete(b"bytes 0-9/2"), ParseProgress::Incomplete);
+    /// assert_eq!(ContentRange::parse_maybe_incomplete(b"bytes 0-a"), ParseProgress::Invalid);
+    /// ```
+    #[must_use]
+    pub fn parse_maybe_incomplete(header: &[u8]) -> ParseProgress {
+        let mut spans = ContentRangeSpans::default();
+        let ends_input = |span: &Option<Range<usize>>| {
+            span.as_ref().map_or(false, |span| span.end == header.len())
+        };
+        match Self::try_parse_prefix(header, &ParseOptions::default(), &mut spans) {
+            Ok((value, end)) if header[end..].iter().all(|&c| is_whitespace(c)) => {
+                ParseProgress::Complete(value)
+            }
+            Err(ParseError::UnexpectedEnd) => ParseProgress::Incomplete,
+            Err(ParseError::UnknownUnit) if PREFIX.starts_with(header) => ParseProgress::Incomplete,
+            // a longer number could still satisfy the invariants
+            Err(ParseError::FirstAfterLast) if ends_input(&spans.last_byte) => {
+                ParseProgress::Incomplete
+            }
+            Err(ParseError::LastBeyondLength) if ends_input(&spans.complete_length) => {
+                ParseProgress::Incomplete
+            }
+            _ => ParseProgress::Invalid,
+        }
+    }
+
+    /// Parses a value at the start of `header` that must end exactly `expected_len` bytes in,
+    /// e.g. a fixed-size field of a binary protocol. Any bytes past the value are ignored.
+    /// Returns `None` if the value is shorter or longer than `expected_len`, including
+    /// a number continuing past the boundary, or trailing blanks.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// assert_eq!(ContentRange::parse_exact(b"bytes 0-9/20...", 12),
+    ///     ContentRange::parse("bytes 0-9/20"));
+    /// assert_eq!(ContentRange::parse_exact(b"bytes 0-9/200", 12), None);
+    /// ```
+    #[must_use]
+    pub fn parse_exact(header: &[u8], expected_len: usize) -> Option<ContentRange> {
+        let mut spans = ContentRangeSpans::default();
+        let (res, end) =
+            Self::try_parse_prefix(header, &ParseOptions::default(), &mut spans).ok()?;
+        fail_if(end != expected_len)?;
+        Some(res)
+    }
+
+    /// Same as [`parse`](Self::parse), but the parsing rules can be adjusted with [`ParseOptions`].
+    ///
+    /// ```
+    /// # use http_content_range::{ContentRange, ContentRangeBytes, ParseOptions};
+    /// let options = ParseOptions { range_separator: b"..", ..ParseOptions::default() };
+    /// assert_eq!(ContentRange::parse_with_options("bytes 0..9/20", &options).unwrap(),
+    ///     ContentRange::Bytes(ContentRangeBytes{first_byte: 0, last_byte: 9, complete_length: 20}));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn parse_with_options(header: &str, options: &ParseOptions<'_>) -> Option<ContentRange> {
+        Self::parse_bytes_with_options(header.as_bytes(), options)
+    }
+
+    /// Same as [`parse_with_options`](Self::parse_with_options) but parses directly from the byte array
+    #[must_use]
+    pub fn parse_bytes_with_options(
+        header: &[u8],
+        options: &ParseOptions<'_>,
+    ) -> Option<ContentRange> {
+        Self::try_parse_with_spans(header, options)
+            .ok()
+            .map(|(value, _)| value)
+    }
+
+    /// Same as [`parse`](Self::parse), but reports why the header could not be parsed.
+    ///
+    /// ```
+    /// # use http_content_range::{ContentRange, ParseError};
+    /// assert!(ContentRange::try_parse("bytes 42-69/420").is_ok());
+    /// assert_eq!(ContentRange::try_parse("bytes 42-69/x"),
+    ///     Err(ParseError::ExpectedNumber { offset: 12 }));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a [`ParseError`] describing the first problem found in `header`.
+    #[inline]
+    pub fn try_parse(header: &str) -> Result<ContentRange, ParseError> {
+        Self::try_parse_bytes(header.as_bytes())
+    }
+
+    /// Same as [`parse_bytes`](Self::parse_bytes), but also returns `"ok"` or the stable
+    /// [`ParseError::code`] of the failure, e.g. for an FFI wrapper.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// assert_eq!(ContentRange::parse_with_code(b"bytes 9-0/20"), (None, "first_gt_last"));
+    /// ```
+    #[must_use]
+    pub fn parse_with_code(header: &[u8]) -> (Option<ContentRange>, &'static str) {
+        match Self::try_parse_bytes(header) {
+            Ok(value) => (Some(value), "ok"),
+            Err(err) => (None, err.code()),
+        }
+    }
+
+    /// Same as [`parse`](Self::parse), but calls `on_fail` with the reason if the header
+    /// could not be parsed, e.g. to log it or count it in metrics.
+    ///
+    /// ```
+    /// # use http_content_range::{ContentRange, ParseError};
+    /// let mut failures = Vec::new();
+    /// let value = ContentRange::parse_or_else("bytes 9-0/20", |err| failures.push(err));
+    /// assert_eq!(value, None);
+    /// assert_eq!(failures, [ParseError::FirstAfterLast]);
+    /// ```
+    pub fn parse_or_else(header: &str, on_fail: impl FnOnce(ParseError)) -> Option<ContentRange> {
+        Self::try_parse(header).map_err(on_fail).ok()
+    }
+
+    /// Parses each of the `headers` with [`try_parse`](Self::try_parse), e.g. to audit
+    /// a corpus of headers collected from many servers. Results are in the input order.
+    #[must_use]
+    pub fn parse_all_reporting(headers: &[&str]) -> Vec<Result<ContentRange, ParseError>> {
+        headers
+            .iter()
+            .map(|header| Self::try_parse(header))
+            .collect()
+    }
+
+    /// Same as [`try_parse`](Self::try_parse) but parses directly from the byte array
+    ///
+    /// # Errors
+    /// Returns a [`ParseError`] describing the first problem found in `header`.
+    pub fn try_parse_bytes(header: &[u8]) -> Result<ContentRange, ParseError> {
+        Self::try_parse_with_spans(header, &ParseOptions::default()).map(|(value, _)| value)
+    }
+
+    /// Same as [`try_parse_bytes`](Self::try_parse_bytes), but the error also carries
+    /// a short snippet of the input around the failure, for logging.
+    /// Unlike the other parsing functions, this one allocates when parsing fails.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// let err = ContentRange::try_parse_verbose(b"bytes 4-69x/420").unwrap_err();
+    /// assert_eq!(err.context, "4-69x/42");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a [`ParseErrorWithContext`] describing the first problem found in `header`.
+    pub fn try_parse_verbose(header: &[u8]) -> Result<ContentRange, ParseErrorWithContext> {
+        Self::try_parse_bytes(header).map_err(|e| e.with_context(header))
+    }
+
+    /// Parses the header like [`parse`](Self::parse), but returns the positions
+    /// of each of its tokens within `header`, e.g. to highlight them in an editor.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// let header = "bytes 42-69/420";
+    /// let spans = ContentRange::parse_spans(header).unwrap();
+    /// assert_eq!(&header[spans.unit], "bytes");
+    /// assert_eq!(&header[spans.first_byte.unwrap()], "42");
+    /// assert_eq!(&header[spans.last_byte.unwrap()], "69");
+    /// assert_eq!(&header[spans.complete_length.unwrap()], "420");
+    /// ```
+    #[must_use]
+    pub fn parse_spans(header: &str) -> Option<ContentRangeSpans> {
+        Self::try_parse_with_spans(header.as_bytes(), &ParseOptions::default())
+            .ok()
+            .map(|(_, spans)| spans)
+    }
+
+    fn try_parse_with_spans(
+        header: &[u8],
+        options: &ParseOptions<'_>,
+    ) -> Result<(ContentRange, ContentRangeSpans), ParseError> {
+        let header = match header {
+            [rest @ .., b'\r', b'\n'] | [rest @ .., b'\n'] if options.strip_trailing_crlf => rest,
+            _ => header,
+        };
+        let mut spans = ContentRangeSpans::default();
+        let (res, end) = Self::try_parse_prefix(header, options, &mut spans)?;
+
+        // verify there is nothing left
+        let mut cur = Cursor::new(header, end, options);
+        match cur.skip_spaces() {
+            None => match options.max_trailing_whitespace {
+                Some(max) if cur.pos() - end > max => {
+                    Err(ParseError::UnexpectedByte { offset: end + max })
+                }
+                _ => Ok((res, spans)),
+            },
+            Some(_) => Err(cur.error()),
         }
+    }
+
+    /// Parses the value at the start of `header`, returning it together with the offset
+    /// right after it. Anything after the value is not inspected.
+    /// `spans` are filled in as the tokens are parsed, even if parsing fails later.
+    fn try_parse_prefix(
+        header: &[u8],
+        options: &ParseOptions<'_>,
+        spans: &mut ContentRangeSpans,
+    ) -> Result<(ContentRange, usize), ParseError> {
+        let unit_start = if options.strip_utf8_bom && header.starts_with(UTF8_BOM) {
+            UTF8_BOM.len()
+        } else {
+            0
+        };
+        let unit_header = &header[unit_start..];
+        let token_len = unit_header
+            .iter()
+            .position(|&c| (options.whitespace)(c))
+            .unwrap_or(unit_header.len());
+        let token = &unit_header[..token_len];
+        let unit = options
+            .unit_aliases
+            .iter()
+            .find(|(alias, _)| *alias == token)
+            .map_or(token, |&(_, canonical)| canonical);
+        let unit_len = if unit == PREFIX {
+            token_len
+        } else if unit_header.starts_with(PREFIX) {
+            // e.g. `bytes1-2/3`, reported as an unexpected byte after the unit
+            PREFIX.len()
+        } else {
+            return Err(ParseError::UnknownUnit);
+        };
 
-        let mut iter = header[PREFIX.len()..].iter().peekable();
+        let mut cur = Cursor::new(header, unit_start + unit_len, options);
+        spans.unit = unit_start..unit_start + unit_len;
+        let parse_u64 = |cur: &mut Cursor<'_, '_>, field, span: &mut Option<_>| {
+            let start = cur.pos();
+            let value = cur.parse_u64(field)?;
+            *span = Some(start..cur.pos());
+            Ok(value)
+        };
 
         // must start with a space
-        fail_if(!is_whitespace(*iter.next()?))?;
-        let res = if iter.skip_spaces()? == b'*' {
+        match cur.peek() {
+            Some(c) if (options.whitespace)(c) => {}
+            _ => return Err(cur.error()),
+        }
+        let res = if cur.skip_spaces() == Some(b'*') {
             // Unsatisfied range
-            iter.next()?; // consume '*'
-            iter.parse_separator(b'/')?;
+            let star = cur.pos();
+            cur.advance(); // consume '*'
+            cur.parse_separator(b"/").map_err(|err| match err {
+                // e.g. `bytes *-9/20`, a byte range with `*` instead of the first byte
+                ParseError::UnexpectedByte { .. } => ParseError::UnexpectedStar { offset: star },
+                err => err,
+            })?;
             ContentRange::Unsatisfied(ContentRangeUnsatisfied {
-                complete_length: iter.parse_u64()?,
+                complete_length: parse_u64(
+                    &mut cur,
+                    Field::CompleteLength,
+                    &mut spans.complete_length,
+                )?,
             })
         } else {
             // byte range
-            let first_byte = iter.parse_u64()?;
-            iter.parse_separator(b'-')?;
-            let last_byte = iter.parse_u64()?;
-            fail_if(first_byte > last_byte)?;
-            if iter.parse_separator(b'/')? == b'*' {
+            let first_byte = parse_u64(&mut cur, Field::FirstByte, &mut spans.first_byte)?;
+            cur.parse_separator(options.range_separator)?;
+            let last_byte = parse_u64(&mut cur, Field::LastByte, &mut spans.last_byte)?;
+            if first_byte > last_byte {
+                return Err(ParseError::FirstAfterLast);
+            }
+            if cur.parse_separator(b"/")? == b'*' {
                 // unbound byte range, consume '*'
-                iter.next()?;
+                cur.advance();
                 ContentRange::UnboundBytes(ContentRangeUnbound {
                     first_byte,
                     last_byte,
                 })
             } else {
-                let complete_length = iter.parse_u64()?;
-                fail_if(last_byte >= complete_length)?;
+                let complete_length =
+                    parse_u64(&mut cur, Field::CompleteLength, &mut spans.complete_length)?;
+                if last_byte >= complete_length {
+                    return Err(ParseError::LastBeyondLength);
+                }
                 ContentRange::Bytes(ContentRangeBytes {
                     first_byte,
                     last_byte,
@@ -152,85 +859,2853 @@ impl ContentRange {
                 })
             }
         };
-
-        // verify there is nothing left
-        match iter.skip_spaces() {
-            None => Some(res),
-            Some(_) => None,
-        }
+        Ok((res, cur.pos()))
     }
-}
-
-#[cfg(test)]
-mod tests {
-    #![allow(clippy::unnecessary_wraps)]
 
-    use super::*;
+    /// Assembles a value from separately provided numeric fields, e.g. from an API
+    /// that returns them as strings. Without `complete`, the result is [`UnboundBytes`](Self::UnboundBytes).
+    ///
+    /// Each field must be a plain decimal number, parsed the same way as in [`parse`](Self::parse).
+    ///
+    /// ```
+    /// # use http_content_range::{ContentRange, ContentRangeBytes, Field, NumberError, ParseError};
+    /// assert_eq!(ContentRange::from_str_parts("0", "9", Some("20")),
+    ///     Ok(ContentRange::Bytes(ContentRangeBytes{first_byte: 0, last_byte: 9, complete_length: 20})));
+    /// assert_eq!(ContentRange::from_str_parts("0", "x", None),
+    ///     Err(ParseError::InvalidNumber { field: Field::LastByte, error: NumberError::InvalidDigit }));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`ParseError::InvalidNumber`] if a field is not a valid number,
+    /// or another [`ParseError`] if the fields do not form a valid range.
+    pub fn from_str_parts(
+        first: &str,
+        last: &str,
+        complete: Option<&str>,
+    ) -> Result<ContentRange, ParseError> {
+        let parse = |value: &str, field| {
+            parse_u64_exact(value.as_bytes())
+                .map_err(|error| ParseError::InvalidNumber { field, error })
+        };
+        let first_byte = parse(first, Field::FirstByte)?;
+        let last_byte = parse(last, Field::LastByte)?;
+        Ok(match complete {
+            Some(complete) => ContentRange::Bytes(ContentRangeBytes::try_from((
+                first_byte,
+                last_byte,
+                parse(complete, Field::CompleteLength)?,
+            ))?),
+            None => {
+                ContentRange::UnboundBytes(ContentRangeUnbound::try_from((first_byte, last_byte))?)
+            }
+        })
+    }
 
-    fn bytes(first_byte: u64, last_byte: u64, complete_length: u64) -> Option<ContentRange> {
-        Some(ContentRange::Bytes(ContentRangeBytes {
-            first_byte,
-            last_byte,
-            complete_length,
-        }))
+    /// Assembles a value of the given `kind` from its numeric fields, e.g. from a simple
+    /// interchange format. Exactly the fields used by `kind` must be present.
+    ///
+    /// ```
+    /// # use http_content_range::{ContentRange, ContentRangeKind, Field, ParseError};
+    /// assert_eq!(ContentRange::from_kind_and_parts(ContentRangeKind::Unsatisfied, None, None, Some(20)).ok(),
+    ///     ContentRange::parse("bytes */20"));
+    /// assert_eq!(ContentRange::from_kind_and_parts(ContentRangeKind::Bytes, Some(0), Some(9), None),
+    ///     Err(ParseError::MissingField { field: Field::CompleteLength }));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`ParseError::MissingField`] or [`ParseError::UnexpectedField`] if the fields
+    /// do not match `kind`, or another [`ParseError`] if they do not form a valid range.
+    pub fn from_kind_and_parts(
+        kind: ContentRangeKind,
+        first: Option<u64>,
+        last: Option<u64>,
+        complete: Option<u64>,
+    ) -> Result<ContentRange, ParseError> {
+        let expect = |value: Option<u64>, present: bool, field| match (value, present) {
+            (Some(value), true) => Ok(value),
+            (None, false) => Ok(0),
+            (None, true) => Err(ParseError::MissingField { field }),
+            (Some(_), false) => Err(ParseError::UnexpectedField { field }),
+        };
+        let has_bounds = kind != ContentRangeKind::Unsatisfied;
+        let has_length = kind != ContentRangeKind::UnboundBytes;
+        let first_byte = expect(first, has_bounds, Field::FirstByte)?;
+        let last_byte = expect(last, has_bounds, Field::LastByte)?;
+        let complete_length = expect(complete, has_length, Field::CompleteLength)?;
+        Ok(match kind {
+            ContentRangeKind::Bytes => ContentRange::Bytes(ContentRangeBytes::try_from((
+                first_byte,
+                last_byte,
+                complete_length,
+            ))?),
+            ContentRangeKind::UnboundBytes => {
+                ContentRange::UnboundBytes(ContentRangeUnbound::try_from((first_byte, last_byte))?)
+            }
+            ContentRangeKind::Unsatisfied => {
+                ContentRange::Unsatisfied(ContentRangeUnsatisfied { complete_length })
+            }
+        })
+    }
+
+    /// Serializes the value as a canonical Content-Range header value, same as its
+    /// [`Display`](fmt::Display) output, but as ASCII bytes.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// let value = ContentRange::parse("bytes 42 - 69 / 420").unwrap();
+    /// assert_eq!(value.to_bytes_vec(), b"bytes 42-69/420");
+    /// ```
+    #[must_use]
+    pub fn to_bytes_vec(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        // writing into a Vec cannot fail
+        let _ = write!(buf, "{self}");
+        buf
+    }
+
+    /// Combines what two responses for the same resource tell about it.
+    ///
+    /// The byte range is taken from `self` if it has one, otherwise from `other`,
+    /// and a missing `complete_length` is filled in from the other value.
+    /// Returns `None` if the two values disagree on `complete_length`,
+    /// or if any of their byte ranges does not fit within it.
+    ///
+    /// ```
+    /// # use http_content_range::{ContentRange, ContentRangeBytes};
+    /// let unbound = ContentRange::parse("bytes 0-9/*").unwrap();
+    /// let unsatisfied = ContentRange::parse("bytes */20").unwrap();
+    /// assert_eq!(unbound.merge_knowledge(&unsatisfied),
+    ///     Some(ContentRange::Bytes(ContentRangeBytes{first_byte: 0, last_byte: 9, complete_length: 20})));
+    /// ```
+    #[must_use]
+    pub fn merge_knowledge(&self, other: &ContentRange) -> Option<ContentRange> {
+        let complete_length = match (self.complete_length(), other.complete_length()) {
+            (Some(a), Some(b)) if a != b => return None,
+            (a, b) => a.or(b),
+        };
+        if let Some(complete_length) = complete_length {
+            for (_, last_byte) in [self.bounds(), other.bounds()].into_iter().flatten() {
+                fail_if(last_byte >= complete_length)?;
+            }
+        }
+        Some(
+            match (self.bounds().or_else(|| other.bounds()), complete_length) {
+                (Some((first_byte, last_byte)), Some(complete_length)) => {
+                    ContentRange::Bytes(ContentRangeBytes {
+                        first_byte,
+                        last_byte,
+                        complete_length,
+                    })
+                }
+                (Some((first_byte, last_byte)), None) => {
+                    ContentRange::UnboundBytes(ContentRangeUnbound {
+                        first_byte,
+                        last_byte,
+                    })
+                }
+                (None, Some(complete_length)) => {
+                    ContentRange::Unsatisfied(ContentRangeUnsatisfied { complete_length })
+                }
+                (None, None) => unreachable!("every variant has either bounds or a length"),
+            },
+        )
+    }
+
+    /// Checks that a byte range response corresponds to the `Range: bytes=req_first-req_last` request.
+    /// An open-ended request (`req_last` is `None`) accepts any `last_byte`.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// let r = ContentRange::parse("bytes 10-19/50").unwrap();
+    /// assert!(r.satisfies_request(10, Some(19)));
+    /// assert!(r.satisfies_request(10, None));
+    /// assert!(!r.satisfies_request(0, Some(19)));
+    /// ```
+    #[must_use]
+    pub fn satisfies_request(&self, req_first: u64, req_last: Option<u64>) -> bool {
+        match self.bounds() {
+            Some((first_byte, last_byte)) => {
+                first_byte == req_first && last_byte == req_last.unwrap_or(last_byte)
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the inner [`ContentRangeBytes`] of a [`ContentRange::Bytes`] value.
+    ///
+    /// # Panics
+    /// Panics with a descriptive message if the value is a different variant.
+    #[must_use]
+    #[track_caller]
+    pub fn expect_bytes(self) -> ContentRangeBytes {
+        match self {
+            ContentRange::Bytes(r) => r,
+            _ => panic!("expected ContentRange::Bytes, got {self:#?}"),
+        }
+    }
+
+    /// Returns the inner [`ContentRangeUnbound`] of a [`ContentRange::UnboundBytes`] value.
+    ///
+    /// # Panics
+    /// Panics with a descriptive message if the value is a different variant.
+    #[must_use]
+    #[track_caller]
+    pub fn expect_unbound(self) -> ContentRangeUnbound {
+        match self {
+            ContentRange::UnboundBytes(r) => r,
+            _ => panic!("expected ContentRange::UnboundBytes, got {self:#?}"),
+        }
+    }
+
+    /// Returns the inner [`ContentRangeUnsatisfied`] of a [`ContentRange::Unsatisfied`] value.
+    ///
+    /// # Panics
+    /// Panics with a descriptive message if the value is a different variant.
+    #[must_use]
+    #[track_caller]
+    pub fn expect_unsatisfied(self) -> ContentRangeUnsatisfied {
+        match self {
+            ContentRange::Unsatisfied(r) => r,
+            _ => panic!("expected ContentRange::Unsatisfied, got {self:#?}"),
+        }
+    }
+
+    /// Returns the inner [`ContentRangeBytes`] of a [`ContentRange::Bytes`] value,
+    /// or gives back the original value for any other variant.
+    ///
+    /// # Errors
+    /// Returns `Err(self)` if the value is not [`ContentRange::Bytes`].
+    pub fn try_into_bytes(self) -> Result<ContentRangeBytes, ContentRange> {
+        match self {
+            ContentRange::Bytes(r) => Ok(r),
+            _ => Err(self),
+        }
+    }
+
+    /// Returns the inner [`ContentRangeUnbound`] of a [`ContentRange::UnboundBytes`] value,
+    /// or gives back the original value for any other variant.
+    ///
+    /// # Errors
+    /// Returns `Err(self)` if the value is not [`ContentRange::UnboundBytes`].
+    pub fn try_into_unbound(self) -> Result<ContentRangeUnbound, ContentRange> {
+        match self {
+            ContentRange::UnboundBytes(r) => Ok(r),
+            _ => Err(self),
+        }
+    }
+
+    /// Returns the inner [`ContentRangeUnsatisfied`] of a [`ContentRange::Unsatisfied`] value,
+    /// or gives back the original value for any other variant.
+    ///
+    /// # Errors
+    /// Returns `Err(self)` if the value is not [`ContentRange::Unsatisfied`].
+    pub fn try_into_unsatisfied(self) -> Result<ContentRangeUnsatisfied, ContentRange> {
+        match self {
+            ContentRange::Unsatisfied(r) => Ok(r),
+            _ => Err(self),
+        }
+    }
+
+    /// Returns a reference to the inner [`ContentRangeBytes`] of a [`ContentRange::Bytes`] value,
+    /// e.g. to compare it with `value.as_bytes() == Some(&expected)`.
+    #[must_use]
+    pub fn as_bytes(&self) -> Option<&ContentRangeBytes> {
+        match self {
+            ContentRange::Bytes(r) => Some(r),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the inner [`ContentRangeUnbound`] of a
+    /// [`ContentRange::UnboundBytes`] value.
+    #[must_use]
+    pub fn as_unbound(&self) -> Option<&ContentRangeUnbound> {
+        match self {
+            ContentRange::UnboundBytes(r) => Some(r),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the inner [`ContentRangeUnsatisfied`] of a
+    /// [`ContentRange::Unsatisfied`] value.
+    #[must_use]
+    pub fn as_unsatisfied(&self) -> Option<&ContentRangeUnsatisfied> {
+        match self {
+            ContentRange::Unsatisfied(r) => Some(r),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the server returned actual bytes,
+    /// i.e. for [`Bytes`](Self::Bytes) and [`UnboundBytes`](Self::UnboundBytes).
+    #[must_use]
+    #[inline]
+    pub fn is_satisfiable(&self) -> bool {
+        matches!(self, ContentRange::Bytes(_) | ContentRange::UnboundBytes(_))
+    }
+
+    /// Returns `true` for `bytes */0`, i.e. the resource is known to be empty,
+    /// so no range of it can ever be satisfied.
+    ///
+    /// An empty resource cannot be described by a byte range: `bytes 0-0/0` is invalid,
+    /// because `last_byte` must be less than `complete_length`.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// assert!(ContentRange::parse("bytes */0").unwrap().is_empty_resource());
+    /// assert!(ContentRange::parse("bytes 0-0/0").is_none());
+    /// ```
+    #[must_use]
+    pub fn is_empty_resource(&self) -> bool {
+        matches!(
+            self,
+            ContentRange::Unsatisfied(ContentRangeUnsatisfied { complete_length: 0 })
+        )
+    }
+
+    /// Returns `true` if the unit of this value matches the unit advertised by the
+    /// `Accept-Ranges` header, e.g. `bytes`. Units are compared case-insensitively.
+    /// All variants use the `bytes` unit, so `Accept-Ranges: none` never matches.
+    ///
+    /// The result does not depend on `self` yet, but it is a method because the unit belongs
+    /// to the value: callers will not need to change if a variant with another unit is added.
+    #[must_use]
+    pub fn unit_matches(&self, accept_ranges_unit: &str) -> bool {
+        accept_ranges_unit
+            .trim_matches([' ', '\t'])
+            .eq_ignore_ascii_case("bytes")
+    }
+
+    /// Returns `true` if `header` parses to this value, allowing any whitespace differences
+    /// accepted by the lenient [`parse`](Self::parse).
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// let range = ContentRange::parse("bytes 0-9/20").unwrap();
+    /// assert!(range.matches_header_ignoring_whitespace("bytes  0 - 9 / 20 "));
+    /// ```
+    #[must_use]
+    pub fn matches_header_ignoring_whitespace(&self, header: &str) -> bool {
+        Self::parse(header).as_ref() == Some(self)
+    }
+
+    /// Returns `true` if `header` is a valid value written in the exact canonical form,
+    /// i.e. it matches its own [`Display`](fmt::Display) output byte for byte,
+    /// without extra whitespace or leading zeros.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// assert!(ContentRange::is_canonical("bytes 0-9/20"));
+    /// assert!(!ContentRange::is_canonical("bytes 0 - 9/20"));
+    /// ```
+    #[must_use]
+    pub fn is_canonical(header: &str) -> bool {
+        Self::parse(header).map_or(false, |value| value.to_string() == header)
+    }
+
+    /// Bounds on the length of the response body, in the style of [`Iterator::size_hint`].
+    ///
+    /// The body length of both byte variants is known exactly, even if the `complete_length`
+    /// is not, so they return `(len, Some(len))`. An unsatisfied response has no body, `(0, Some(0))`.
+    /// If the length does not fit into `u64` (only possible for `0-18446744073709551615`),
+    /// `(u64::MAX, None)` is returned. A range built by hand with `first_byte > last_byte`
+    /// has no meaningful length, and returns `(0, None)`.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// assert_eq!(ContentRange::parse("bytes 10-19/*").unwrap().len_hint(), (10, Some(10)));
+    /// assert_eq!(ContentRange::parse("bytes */20").unwrap().len_hint(), (0, Some(0)));
+    /// ```
+    #[must_use]
+    pub fn len_hint(&self) -> (u64, Option<u64>) {
+        match self.bounds() {
+            Some((first_byte, last_byte)) => match last_byte.checked_sub(first_byte) {
+                Some(diff) => match diff.checked_add(1) {
+                    Some(len) => (len, Some(len)),
+                    None => (u64::MAX, None),
+                },
+                None => (0, None),
+            },
+            None => (0, Some(0)),
+        }
+    }
+
+    /// Returns `true` for a byte range whose `last_byte` is the last byte of the resource.
+    /// This is valid, but may be worth flagging when auditing server responses.
+    /// See [`ContentRangeBytes::touches_end`].
+    #[must_use]
+    pub fn touches_end(&self) -> bool {
+        match self {
+            ContentRange::Bytes(r) => r.touches_end(),
+            ContentRange::UnboundBytes(_) | ContentRange::Unsatisfied(_) => false,
+        }
+    }
+
+    /// Same as [`complete_length`](Self::complete_length), meant to be used as a component
+    /// of a cache key for the resource, together with the caller's own resource identifier.
+    #[must_use]
+    #[inline]
+    pub fn resource_key(&self) -> Option<u64> {
+        self.complete_length()
+    }
+
+    /// Returns the byte bounds of both [`Bytes`](Self::Bytes) and [`UnboundBytes`](Self::UnboundBytes)
+    /// as a single [`ByteView`], or `None` for [`Unsatisfied`](Self::Unsatisfied).
+    ///
+    /// ```
+    /// # use http_content_range::{ByteView, ContentRange};
+    /// let view = ContentRange::parse("bytes 0-9/*").unwrap().byte_view().unwrap();
+    /// assert_eq!(view, ByteView { first_byte: 0, last_byte: 9, complete_length: None });
+    /// ```
+    #[must_use]
+    pub fn byte_view(&self) -> Option<ByteView> {
+        let (first_byte, last_byte) = self.bounds()?;
+        Some(ByteView {
+            first_byte,
+            last_byte,
+            complete_length: self.complete_length(),
+        })
+    }
+
+    /// The `(first_byte, last_byte)` pair of the byte variants
+    fn bounds(&self) -> Option<(u64, u64)> {
+        match *self {
+            ContentRange::Bytes(r) => Some((r.first_byte, r.last_byte)),
+            ContentRange::UnboundBytes(r) => Some((r.first_byte, r.last_byte)),
+            ContentRange::Unsatisfied(_) => None,
+        }
+    }
+
+    /// The `complete_length` of the resource, if known (for [`Bytes`](Self::Bytes)
+    /// and [`Unsatisfied`](Self::Unsatisfied)).
+    #[must_use]
+    pub fn complete_length(&self) -> Option<u64> {
+        match *self {
+            ContentRange::Bytes(r) => Some(r.complete_length),
+            ContentRange::UnboundBytes(_) => None,
+            ContentRange::Unsatisfied(r) => Some(r.complete_length),
+        }
+    }
+
+    /// Returns `(first_byte, last_byte, complete_length)`, e.g. for tabular export.
+    ///
+    /// This is lossy: fields the variant does not have are reported as `0`, i.e. the
+    /// `complete_length` of [`UnboundBytes`](Self::UnboundBytes), and both byte positions
+    /// of [`Unsatisfied`](Self::Unsatisfied). Use [`byte_view`](Self::byte_view) or
+    /// [`complete_length`](Self::complete_length) to tell them apart from real zeros.
+    #[must_use]
+    pub fn to_triple(&self) -> (u64, u64, u64) {
+        let (first_byte, last_byte) = self.bounds().unwrap_or((0, 0));
+        (first_byte, last_byte, self.complete_length().unwrap_or(0))
+    }
+
+    /// Applies `f` to every byte position and length in this value, e.g. to remap offsets
+    /// between encoded and decoded representations.
+    /// Returns `None` if the mapped values break the range invariants.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRange;
+    /// let range = ContentRange::parse("bytes 5-9/20").unwrap();
+    /// assert_eq!(range.map_offsets(|v| v * 2), ContentRange::parse("bytes 10-18/40"));
+    /// ```
+    #[must_use]
+    pub fn map_offsets(&self, f: impl Fn(u64) -> u64) -> Option<ContentRange> {
+        Some(match *self {
+            ContentRange::Bytes(r) => ContentRange::Bytes(
+                (f(r.first_byte), f(r.last_byte), f(r.complete_length))
+                    .try_into()
+                    .ok()?,
+            ),
+            ContentRange::UnboundBytes(r) => {
+                ContentRange::UnboundBytes((f(r.first_byte), f(r.last_byte)).try_into().ok()?)
+            }
+            ContentRange::Unsatisfied(r) => ContentRange::Unsatisfied(ContentRangeUnsatisfied {
+                complete_length: f(r.complete_length),
+            }),
+        })
+    }
+}
+
+/// Builds the `Content-Range` a server should return for a `Range: bytes=<req_first>-<req_last>`
+/// request of a resource with the given `complete_length`, i.e. the server-side counterpart
+/// to parsing. An open-ended request (`req_last` is `None`) or one past the end of
+/// the resource is clamped to the last byte. Returns [`ContentRange::Unsatisfied`] if
+/// `req_first` is not within the resource, or `req_last` is before `req_first`.
+///
+/// A suffix request for the last `n` bytes (`Range: bytes=-<n>`) is an open-ended request
+/// starting at `complete_length.saturating_sub(n)`.
+///
+/// ```
+/// # use http_content_range::{synthesize_content_range, ContentRange};
+/// assert_eq!(Some(synthesize_content_range(10, Some(99), 50)),
+///     ContentRange::parse("bytes 10-49/50"));
+/// assert_eq!(Some(synthesize_content_range(50, None, 50)), ContentRange::parse("bytes */50"));
+/// ```
+#[must_use]
+pub fn synthesize_content_range(
+    req_first: u64,
+    req_last: Option<u64>,
+    complete_length: u64,
+) -> ContentRange {
+    let last_byte = req_last
+        .unwrap_or(u64::MAX)
+        .min(complete_length.saturating_sub(1));
+    match ContentRangeBytes::try_from((req_first, last_byte, complete_length)) {
+        Ok(r) if req_last.map_or(true, |last| last >= req_first) => ContentRange::Bytes(r),
+        _ => ContentRange::Unsatisfied(ContentRangeUnsatisfied { complete_length }),
+    }
+}
+
+impl ContentRangeBytes {
+    /// Returns `true` if the range ends at the last byte of the resource,
+    /// i.e. `last_byte + 1 == complete_length`.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// assert!(ContentRangeBytes { first_byte: 10, last_byte: 19, complete_length: 20 }.touches_end());
+    /// assert!(!ContentRangeBytes { first_byte: 10, last_byte: 18, complete_length: 20 }.touches_end());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn touches_end(&self) -> bool {
+        self.last_byte.checked_add(1) == Some(self.complete_length)
+    }
+
+    /// Returns `true` if the range covers exactly one byte, i.e. `first_byte == last_byte`.
+    #[must_use]
+    #[inline]
+    pub fn is_single_byte(&self) -> bool {
+        self.first_byte == self.last_byte
+    }
+
+    /// Returns a copy with a different `first_byte`, or `None` if it would break
+    /// the `first_byte <= last_byte < complete_length` invariant.
+    #[must_use]
+    pub fn with_first_byte(&self, first_byte: u64) -> Option<ContentRangeBytes> {
+        Self::try_from((first_byte, self.last_byte, self.complete_length)).ok()
+    }
+
+    /// Returns a copy with a different `last_byte`, or `None` if it would break
+    /// the `first_byte <= last_byte < complete_length` invariant.
+    #[must_use]
+    pub fn with_last_byte(&self, last_byte: u64) -> Option<ContentRangeBytes> {
+        Self::try_from((self.first_byte, last_byte, self.complete_length)).ok()
+    }
+
+    /// Returns a copy with a different `complete_length`, or `None` if it would break
+    /// the `first_byte <= last_byte < complete_length` invariant.
+    #[must_use]
+    pub fn with_complete_length(&self, complete_length: u64) -> Option<ContentRangeBytes> {
+        Self::try_from((self.first_byte, self.last_byte, complete_length)).ok()
+    }
+
+    /// Splits the range into consecutive sub-ranges of at most `size` bytes,
+    /// all sharing the original `complete_length`. The last chunk may be smaller.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// let r = ContentRangeBytes { first_byte: 0, last_byte: 9, complete_length: 20 };
+    /// let lengths: Vec<_> = r.chunks(4).map(|c| c.last_byte - c.first_byte + 1).collect();
+    /// assert_eq!(lengths, [4, 4, 2]);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `size` is 0, same as [`slice::chunks`].
+    pub fn chunks(&self, size: u64) -> impl Iterator<Item = ContentRangeBytes> {
+        assert!(size != 0, "chunk size must be non-zero");
+        let range = *self;
+        let mut next = Some(range.first_byte);
+        std::iter::from_fn(move || {
+            let first_byte = next?;
+            let last_byte = first_byte.saturating_add(size - 1).min(range.last_byte);
+            next = if last_byte < range.last_byte {
+                Some(last_byte + 1)
+            } else {
+                None
+            };
+            Some(ContentRangeBytes {
+                first_byte,
+                last_byte,
+                complete_length: range.complete_length,
+            })
+        })
+    }
+
+    /// Returns `true` if one range starts right after the other one ends, in either order.
+    /// Overlapping ranges are not adjacent.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// let a = ContentRangeBytes { first_byte: 0, last_byte: 9, complete_length: 20 };
+    /// let b = ContentRangeBytes { first_byte: 10, last_byte: 19, complete_length: 20 };
+    /// assert!(a.is_adjacent_to(&b));
+    /// assert!(b.is_adjacent_to(&a));
+    /// ```
+    #[must_use]
+    pub fn is_adjacent_to(&self, other: &ContentRangeBytes) -> bool {
+        self.last_byte.checked_add(1) == Some(other.first_byte)
+            || other.last_byte.checked_add(1) == Some(self.first_byte)
+    }
+
+    /// Returns `true` if the range shares at least one byte with the
+    /// `Range: bytes=req_first-req_last` request. An open-ended request (`req_last` is `None`)
+    /// extends to the end of the resource. Unlike [`ContentRange::satisfies_request`],
+    /// the bounds do not need to match exactly.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// let r = ContentRangeBytes { first_byte: 10, last_byte: 19, complete_length: 50 };
+    /// assert!(r.overlaps_request(15, Some(30)));
+    /// assert!(!r.overlaps_request(20, None));
+    /// ```
+    #[must_use]
+    pub fn overlaps_request(&self, req_first: u64, req_last: Option<u64>) -> bool {
+        let req_last = req_last.unwrap_or(self.complete_length.saturating_sub(1));
+        req_first <= req_last && req_first <= self.last_byte && self.first_byte <= req_last
+    }
+
+    /// Returns `true` if every byte of the range lies inside `window`.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// let r = ContentRangeBytes { first_byte: 10, last_byte: 19, complete_length: 50 };
+    /// assert!(r.is_within(0..=19));
+    /// assert!(!r.is_within(15..=30));
+    /// ```
+    #[must_use]
+    pub fn is_within(&self, window: RangeInclusive<u64>) -> bool {
+        window.contains(&self.first_byte) && window.contains(&self.last_byte)
+    }
+
+    /// Number of additional `chunk_size` requests needed to download the rest of the resource
+    /// after this range, i.e. bytes `last_byte + 1` to `complete_length - 1`.
+    /// Returns `Some(0)` if this range reaches the end, and `None` if `chunk_size` is 0
+    /// or `last_byte` is not less than `complete_length`.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// let r = ContentRangeBytes { first_byte: 0, last_byte: 9, complete_length: 100 };
+    /// assert_eq!(r.chunks_remaining(30), Some(3));
+    /// ```
+    #[must_use]
+    pub fn chunks_remaining(&self, chunk_size: u64) -> Option<u64> {
+        fail_if(chunk_size == 0)?;
+        let remaining = self
+            .complete_length
+            .checked_sub(self.last_byte)?
+            .checked_sub(1)?;
+        Some(remaining / chunk_size + u64::from(remaining % chunk_size != 0))
+    }
+
+    /// The range of at most `chunk_size` bytes to request right after this one,
+    /// e.g. to drive the loop of a resumable download.
+    /// Returns `None` if this range reaches (or is past) the end of the resource,
+    /// or if `chunk_size` is 0.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// let r = ContentRangeBytes { first_byte: 0, last_byte: 9, complete_length: 25 };
+    /// assert_eq!(r.next_chunk(10),
+    ///     Some(ContentRangeBytes { first_byte: 10, last_byte: 19, complete_length: 25 }));
+    /// ```
+    #[must_use]
+    pub fn next_chunk(&self, chunk_size: u64) -> Option<ContentRangeBytes> {
+        let first_byte = self.last_byte.checked_add(1)?;
+        fail_if(chunk_size == 0 || first_byte >= self.complete_length)?;
+        Some(ContentRangeBytes {
+            first_byte,
+            last_byte: first_byte
+                .saturating_add(chunk_size - 1)
+                .min(self.complete_length - 1),
+            complete_length: self.complete_length,
+        })
+    }
+
+    /// The `Range` request header value for the [`next_chunk`](Self::next_chunk) after this one,
+    /// e.g. `bytes=10-19`. Returns `None` if this range reaches the end of the resource,
+    /// or if `chunk_size` is 0.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// let r = ContentRangeBytes { first_byte: 0, last_byte: 9, complete_length: 25 };
+    /// assert_eq!(r.to_next_range_request(10).as_deref(), Some("bytes=10-19"));
+    /// ```
+    #[must_use]
+    pub fn to_next_range_request(&self, chunk_size: u64) -> Option<String> {
+        let next = self.next_chunk(chunk_size)?;
+        Some(format!("bytes={}-{}", next.first_byte, next.last_byte))
+    }
+
+    /// Maps an offset within the response body to the absolute position in the resource.
+    /// Returns `None` if the offset is past the end of this range.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// let r = ContentRangeBytes { first_byte: 10, last_byte: 19, complete_length: 50 };
+    /// assert_eq!(r.absolute_position(0), Some(10));
+    /// assert_eq!(r.absolute_position(9), Some(19));
+    /// assert_eq!(r.absolute_position(10), None);
+    /// ```
+    #[must_use]
+    pub fn absolute_position(&self, body_offset: u64) -> Option<u64> {
+        let position = self.first_byte.checked_add(body_offset)?;
+        fail_if(position > self.last_byte)?;
+        Some(position)
+    }
+
+    /// How far `last_byte` is through the resource, as a whole percentage from 0 to 100,
+    /// rounded down. Returns 100 if `complete_length` is 0.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// let r = ContentRangeBytes { first_byte: 0, last_byte: 49, complete_length: 200 };
+    /// assert_eq!(r.progress_percent(), 25);
+    /// ```
+    #[must_use]
+    pub fn progress_percent(&self) -> u8 {
+        if self.complete_length == 0 {
+            return 100;
+        }
+        let percent = (u128::from(self.last_byte) + 1) * 100 / u128::from(self.complete_length);
+        u8::try_from(percent.min(100)).unwrap_or(100)
+    }
+
+    /// Number of bytes in the range, `last_byte - first_byte + 1`, computed as `u128`
+    /// so that it never overflows, even for `0..=u64::MAX`.
+    /// Returns 0 for a range built by hand with `first_byte > last_byte`.
+    #[must_use]
+    pub fn content_length_u128(&self) -> u128 {
+        (u128::from(self.last_byte) + 1).saturating_sub(u128::from(self.first_byte))
+    }
+
+    /// Translates the range to offsets relative to `base`, e.g. from positions within a container
+    /// to positions within an object stored in it starting at `base`.
+    /// Returns `None` if `base` is past `first_byte`, or the range is not within `complete_length`.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// let r = ContentRangeBytes { first_byte: 110, last_byte: 119, complete_length: 200 };
+    /// assert_eq!(r.relative_to(100),
+    ///     Some(ContentRangeBytes { first_byte: 10, last_byte: 19, complete_length: 100 }));
+    /// ```
+    #[must_use]
+    pub fn relative_to(&self, base: u64) -> Option<ContentRangeBytes> {
+        Self::try_from((
+            self.first_byte.checked_sub(base)?,
+            self.last_byte.checked_sub(base)?,
+            self.complete_length.checked_sub(base)?,
+        ))
+        .ok()
+    }
+
+    /// Extends the range to `boundary` multiples, e.g. for block-aligned reads: `first_byte` is
+    /// rounded down, and `last_byte` up to the end of its block, but no further than the last byte
+    /// of the resource. Returns `None` if `boundary` is 0, or if the range built by hand breaks
+    /// the `first_byte <= last_byte < complete_length` invariant.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// let r = ContentRangeBytes { first_byte: 600, last_byte: 1100, complete_length: 2000 };
+    /// assert_eq!(r.align_to(512),
+    ///     Some(ContentRangeBytes { first_byte: 512, last_byte: 1535, complete_length: 2000 }));
+    /// ```
+    #[must_use]
+    pub fn align_to(&self, boundary: u64) -> Option<ContentRangeBytes> {
+        fail_if(boundary == 0)?;
+        Self::try_from((self.first_byte, self.last_byte, self.complete_length)).ok()?;
+        let block_end = (self.last_byte - self.last_byte % boundary).saturating_add(boundary - 1);
+        Self::try_from((
+            self.first_byte - self.first_byte % boundary,
+            block_end.min(self.complete_length - 1),
+            self.complete_length,
+        ))
+        .ok()
+    }
+
+    /// Converts the byte range into a range of `block_size` blocks, e.g. 512-byte disk sectors.
+    /// The result covers every block touched by this range: positions are rounded down,
+    /// and `complete_length` is rounded up to include a partial last block.
+    /// Returns `None` if `block_size` is 0.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// let r = ContentRangeBytes { first_byte: 600, last_byte: 1100, complete_length: 2000 };
+    /// assert_eq!(r.scale_down(512),
+    ///     Some(ContentRangeBytes { first_byte: 1, last_byte: 2, complete_length: 4 }));
+    /// ```
+    #[must_use]
+    pub fn scale_down(&self, block_size: u64) -> Option<ContentRangeBytes> {
+        fail_if(block_size == 0)?;
+        let complete_length =
+            self.complete_length / block_size + u64::from(self.complete_length % block_size != 0);
+        Self::try_from((
+            self.first_byte / block_size,
+            self.last_byte / block_size,
+            complete_length,
+        ))
+        .ok()
+    }
+
+    /// Packs the range into a fixed 24 byte binary form: `first_byte`, `last_byte`,
+    /// and `complete_length` as little-endian `u64` values, in that order.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeBytes;
+    /// let r = ContentRangeBytes { first_byte: 0, last_byte: 9, complete_length: 20 };
+    /// assert_eq!(ContentRangeBytes::from_le_bytes(&r.to_le_bytes()), Some(r));
+    /// ```
+    #[must_use]
+    pub fn to_le_bytes(&self) -> [u8; 24] {
+        let mut buf = [0; 24];
+        buf[..8].copy_from_slice(&self.first_byte.to_le_bytes());
+        buf[8..16].copy_from_slice(&self.last_byte.to_le_bytes());
+        buf[16..].copy_from_slice(&self.complete_length.to_le_bytes());
+        buf
+    }
+
+    /// Reads a range packed by [`to_le_bytes`](Self::to_le_bytes).
+    /// Returns `None` if the values break the `first_byte <= last_byte < complete_length` invariant.
+    #[must_use]
+    pub fn from_le_bytes(buf: &[u8; 24]) -> Option<ContentRangeBytes> {
+        let value = |idx: usize| {
+            let mut word = [0; 8];
+            word.copy_from_slice(&buf[idx * 8..(idx + 1) * 8]);
+            u64::from_le_bytes(word)
+        };
+        Self::try_from((value(0), value(1), value(2))).ok()
+    }
+}
+
+impl ContentRangeUnbound {
+    /// Returns `true` if the range covers exactly one byte, i.e. `first_byte == last_byte`.
+    #[must_use]
+    #[inline]
+    pub fn is_single_byte(&self) -> bool {
+        self.first_byte == self.last_byte
+    }
+
+    /// Infers the complete length from the length of a body that was read to the end of the resource.
+    /// This is only possible if the range starts at the beginning of the resource.
+    ///
+    /// Returns `None` if `first_byte` is not 0, or if `body_len` is not exactly the length
+    /// of this range, `last_byte + 1`.
+    ///
+    /// ```
+    /// # use http_content_range::{ContentRangeBytes, ContentRangeUnbound};
+    /// let r = ContentRangeUnbound { first_byte: 0, last_byte: 9 };
+    /// assert_eq!(r.infer_complete_length(10),
+    ///     Some(ContentRangeBytes { first_byte: 0, last_byte: 9, complete_length: 10 }));
+    /// ```
+    #[must_use]
+    pub fn infer_complete_length(&self, body_len: u64) -> Option<ContentRangeBytes> {
+        fail_if(self.first_byte != 0 || self.last_byte.checked_add(1) != Some(body_len))?;
+        ContentRangeBytes::try_from((self.first_byte, self.last_byte, body_len)).ok()
+    }
+}
+
+impl ContentRangeUnsatisfied {
+    /// Checks that this 416 response is legitimate for a request starting at `requested_first`,
+    /// i.e. that the requested range begins at or beyond the end of the resource.
+    /// For an empty resource (`complete_length == 0`) every request is justified.
+    ///
+    /// ```
+    /// # use http_content_range::ContentRangeUnsatisfied;
+    /// let r = ContentRangeUnsatisfied { complete_length: 20 };
+    /// assert!(r.justifies(20));
+    /// assert!(!r.justifies(19));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn justifies(&self, requested_first: u64) -> bool {
+        requested_first >= self.complete_length
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unnecessary_wraps)]
+
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn bytes(first_byte: u64, last_byte: u64, complete_length: u64) -> Option<ContentRange> {
+        Some(ContentRange::Bytes(ContentRangeBytes {
+            first_byte,
+            last_byte,
+            complete_length,
+        }))
     }
 
     fn unbound(first_byte: u64, last_byte: u64) -> Option<ContentRange> {
         Some(ContentRange::UnboundBytes(ContentRangeUnbound {
             first_byte,
             last_byte,
-        }))
+        }))
+    }
+
+    fn unsatisfied(complete_length: u64) -> Option<ContentRange> {
+        Some(ContentRange::Unsatisfied(ContentRangeUnsatisfied {
+            complete_length,
+        }))
+    }
+
+    #[test]
+    fn test_parse() {
+        for (header, expected) in vec![
+            // Valid
+            ("bytes 0-9/20", bytes(0, 9, 20)),
+            ("bytes\t 0 \t -\t \t  \t9 / 20   ", bytes(0, 9, 20)),
+            ("bytes */20", unsatisfied(20)),
+            ("bytes   *\t\t/  20    ", unsatisfied(20)),
+            ("bytes */0", unsatisfied(0)),
+            ("bytes 0-9/*", unbound(0, 9)),
+            ("bytes   0  -    9  /  *   ", unbound(0, 9)),
+            //
+            // Errors
+            //
+            ("", None),
+            ("b", None),
+            ("foo", None),
+            ("foo 1-2/3", None),
+            (" bytes 1-2/3", None),
+            ("bytes -2/3", None),
+            ("bytes 1-/3", None),
+            ("bytes 1-2/", None),
+            ("bytes 1-2/a", None),
+            ("bytes1-2/3", None),
+            ("bytes=1-2/3", None),
+            ("bytes a-2/3", None),
+            ("bytes 1-a/3", None),
+            ("bytes 0x01-0x02/3", None),
+            ("bytes 1-2/a", None),
+            (
+                "bytes 1111111111111111111111111111111111111111111-2/1",
+                None,
+            ),
+            ("bytes 1-3/20 1", None),
+            ("bytes 1-3/* 1", None),
+            ("bytes */1 1", None),
+            ("bytes 1-0/20", None),
+            ("bytes 1-20/20", None),
+            ("bytes 1-21/20", None),
+            ("bytes 0-0/0", None),
+        ] {
+            assert_eq!(ContentRange::parse(header), expected);
+            assert_eq!(ContentRange::try_from(header).ok(), expected);
+            assert_eq!(ContentRange::from_str(header).ok(), expected);
+            assert_eq!(ContentRange::try_from(header.as_bytes()).ok(), expected);
+            assert_eq!(ContentRange::parse_ascii(header.as_bytes()), expected);
+            if let Some(value) = expected {
+                assert_eq!(ContentRange::parse(&value.to_string()), expected);
+                assert_eq!(ContentRange::parse_bytes(&value.to_bytes_vec()), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_default() {
+        let r = ContentRangeBytes::default();
+        assert_eq!(r.to_string(), "bytes 0-0/1");
+        assert_eq!(
+            ContentRangeBytes::try_from((r.first_byte, r.last_byte, r.complete_length)),
+            Ok(r)
+        );
+        let r = ContentRangeUnbound::default();
+        assert_eq!(r.to_string(), "bytes 0-0/*");
+        assert_eq!(
+            ContentRangeUnbound::try_from((r.first_byte, r.last_byte)),
+            Ok(r)
+        );
+        let r = ContentRangeUnsatisfied::default();
+        assert_eq!(
+            ContentRange::parse("bytes */0"),
+            Some(ContentRange::Unsatisfied(r))
+        );
+    }
+
+    #[test]
+    fn test_into_range_inclusive() {
+        let range: RangeInclusive<u64> = ContentRange::parse("bytes 42-69/420")
+            .unwrap()
+            .expect_bytes()
+            .into();
+        assert_eq!((*range.start(), *range.end()), (42, 69));
+        assert!(range.contains(&50));
+        let range: RangeInclusive<u64> = ContentRange::parse("bytes 42-69/*")
+            .unwrap()
+            .expect_unbound()
+            .into();
+        assert_eq!(range, 42..=69);
+    }
+
+    #[test]
+    fn test_debug() {
+        for (value, normal, alternate) in [
+            (
+                bytes(42, 69, 420),
+                "Bytes(ContentRangeBytes { first_byte: 42, last_byte: 69, complete_length: 420 })",
+                "ContentRange::Bytes { 42..=69 / 420 }",
+            ),
+            (
+                unbound(42, 69),
+                "UnboundBytes(ContentRangeUnbound { first_byte: 42, last_byte: 69 })",
+                "ContentRange::UnboundBytes { 42..=69 / * }",
+            ),
+            (
+                unsatisfied(420),
+                "Unsatisfied(ContentRangeUnsatisfied { complete_length: 420 })",
+                "ContentRange::Unsatisfied { * / 420 }",
+            ),
+        ] {
+            let value = value.unwrap();
+            assert_eq!(format!("{value:?}"), normal);
+            assert_eq!(format!("{value:#?}"), alternate);
+        }
+    }
+
+    #[test]
+    fn test_try_from_tuple() {
+        for (tuple, expected) in [
+            ((0, 0, 1), Ok(bytes(0, 0, 1))),
+            ((0, 9, 20), Ok(bytes(0, 9, 20))),
+            ((10, 9, 20), Err(ParseError::FirstAfterLast)),
+            ((0, 20, 20), Err(ParseError::LastBeyondLength)),
+            ((0, 0, 0), Err(ParseError::LastBeyondLength)),
+        ] {
+            let value = ContentRangeBytes::try_from(tuple).map(|v| Some(ContentRange::Bytes(v)));
+            assert_eq!(value, expected);
+        }
+        for (tuple, expected) in [
+            ((0, 9), Ok(unbound(0, 9))),
+            ((9, 9), Ok(unbound(9, 9))),
+            ((10, 9), Err(ParseError::FirstAfterLast)),
+        ] {
+            let value =
+                ContentRangeUnbound::try_from(tuple).map(|v| Some(ContentRange::UnboundBytes(v)));
+            assert_eq!(value, expected);
+        }
+    }
+
+    #[test]
+    fn test_from_str_parts() {
+        use Field::*;
+        use NumberError::*;
+        use ParseError::*;
+        let invalid = |field, error| Err(InvalidNumber { field, error });
+        for (first, last, complete, expected) in [
+            ("0", "9", Some("20"), Ok(bytes(0, 9, 20))),
+            ("42", "69", Some("420"), Ok(bytes(42, 69, 420))),
+            ("0", "9", None, Ok(unbound(0, 9))),
+            ("9", "0", None, Err(FirstAfterLast)),
+            ("0", "20", Some("20"), Err(LastBeyondLength)),
+            (
+                "18446744073709551616",
+                "0",
+                None,
+                invalid(FirstByte, Overflow),
+            ),
+            (
+                "0",
+                "9",
+                Some("99999999999999999999"),
+                invalid(CompleteLength, Overflow),
+            ),
+            ("", "9", None, invalid(FirstByte, Empty)),
+            ("0", " 9", None, invalid(LastByte, InvalidDigit)),
+            (
+                "0",
+                "9",
+                Some("0x20"),
+                invalid(CompleteLength, InvalidDigit),
+            ),
+        ] {
+            let value = ContentRange::from_str_parts(first, last, complete);
+            assert_eq!(value.map(Some), expected);
+        }
+    }
+
+    #[test]
+    fn test_error_source() {
+        use std::error::Error as _;
+
+        let err = ContentRange::from_str_parts("0", "99999999999999999999", None).unwrap_err();
+        let source = err.source().unwrap();
+        assert_eq!(source.downcast_ref(), Some(&NumberError::Overflow));
+        assert_eq!(source.to_string(), "number too large to fit in u64");
+        assert!(ParseError::FirstAfterLast.source().is_none());
+    }
+
+    #[test]
+    fn test_as_variant() {
+        let b = ContentRangeBytes {
+            first_byte: 0,
+            last_byte: 9,
+            complete_length: 20,
+        };
+        let u = ContentRangeUnbound {
+            first_byte: 0,
+            last_byte: 9,
+        };
+        let s = ContentRangeUnsatisfied {
+            complete_length: 20,
+        };
+
+        let value = ContentRange::parse("bytes 0-9/20").unwrap();
+        assert_eq!(value.as_bytes(), Some(&b));
+        assert_eq!(value.as_unbound(), None);
+        assert_eq!(value.as_unsatisfied(), None);
+        assert_ne!(value.as_bytes(), b.with_complete_length(30).as_ref());
+
+        let value = ContentRange::parse("bytes 0-9/*").unwrap();
+        assert_eq!(value.as_bytes(), None);
+        assert_eq!(value.as_unbound(), Some(&u));
+        assert_eq!(value.as_unsatisfied(), None);
+
+        let value = ContentRange::parse("bytes */20").unwrap();
+        assert_eq!(value.as_bytes(), None);
+        assert_eq!(value.as_unbound(), None);
+        assert_eq!(value.as_unsatisfied(), Some(&s));
+    }
+
+    #[test]
+    fn test_display() {
+        for (value, expected) in [
+            (bytes(42, 69, 420), "bytes 42-69/420"),
+            (unbound(42, 69), "bytes 42-69/*"),
+            (unsatisfied(420), "bytes */420"),
+        ] {
+            assert_eq!(value.unwrap().to_string(), expected);
+            assert_eq!(value.unwrap().to_bytes_vec(), expected.as_bytes());
+        }
+        let value = ContentRangeUnsatisfied {
+            complete_length: 420,
+        };
+        assert_eq!(value.to_string(), "bytes */420");
+    }
+
+    #[test]
+    fn test_parse_retaining() {
+        let parsed = ContentRange::parse_retaining("bytes 0 - 9/20");
+        assert_eq!(parsed.value, bytes(0, 9, 20));
+        assert_eq!(parsed.source, "bytes 0 - 9/20");
+        assert_eq!(
+            parsed.to_string(),
+            "bytes 0-9/20 (parsed from `bytes 0 - 9/20`)"
+        );
+
+        let parsed = ContentRange::parse_retaining("bytes 9-0/20");
+        assert_eq!(parsed.value, None);
+        assert_eq!(parsed.source, "bytes 9-0/20");
+        assert_eq!(parsed.to_string(), "invalid Content-Range `bytes 9-0/20`");
+    }
+
+    #[test]
+    fn test_parse_spans() {
+        fn tokens(header: &str) -> (&str, Option<&str>, Option<&str>, Option<&str>) {
+            let spans = ContentRange::parse_spans(header).unwrap();
+            let token = |span: Option<Range<usize>>| span.map(|v| &header[v]);
+            (
+                &header[spans.unit],
+                token(spans.first_byte),
+                token(spans.last_byte),
+                token(spans.complete_length),
+            )
+        }
+        assert_eq!(
+            tokens("bytes 42-69/420"),
+            ("bytes", Some("42"), Some("69"), Some("420"))
+        );
+        assert_eq!(
+            tokens("bytes\t 0 \t -\t 9 / 20   "),
+            ("bytes", Some("0"), Some("9"), Some("20"))
+        );
+        assert_eq!(
+            tokens("bytes 42-69/*"),
+            ("bytes", Some("42"), Some("69"), None)
+        );
+        assert_eq!(tokens("bytes  */420"), ("bytes", None, None, Some("420")));
+        assert_eq!(ContentRange::parse_spans("bytes 42-69/4x"), None);
+    }
+
+    #[test]
+    fn test_range_separator() {
+        let options = ParseOptions {
+            range_separator: b"..",
+            ..ParseOptions::default()
+        };
+        for (header, expected) in [
+            ("bytes 0..9/20", bytes(0, 9, 20)),
+            ("bytes 0 .. 9/*", unbound(0, 9)),
+            ("bytes */20", unsatisfied(20)),
+            ("bytes 0-9/20", None),
+            ("bytes 0.9/20", None),
+            ("bytes 0. .9/20", None),
+        ] {
+            assert_eq!(ContentRange::parse_with_options(header, &options), expected);
+        }
+        assert_eq!(ContentRange::parse("bytes 0..9/20"), None);
+    }
+
+    #[test]
+    fn test_try_parse() {
+        use ParseError::*;
+        for (header, expected) in [
+            ("", UnknownUnit),
+            ("foo 1-2/3", UnknownUnit),
+            (" bytes 1-2/3", UnknownUnit),
+            ("bytes", UnexpectedEnd),
+            ("bytes ", UnexpectedEnd),
+            ("bytes 1-2/", UnexpectedEnd),
+            ("bytes 1-2", UnexpectedEnd),
+            ("bytes *", UnexpectedEnd),
+            ("bytes1-2/3", UnexpectedByte { offset: 5 }),
+            ("bytes=1-2/3", UnexpectedByte { offset: 5 }),
+            ("bytes 1+2/3", UnexpectedByte { offset: 7 }),
+            ("bytes 1-3/20 1", UnexpectedByte { offset: 13 }),
+            ("bytes *-2/3", UnexpectedStar { offset: 6 }),
+            ("bytes *-9/20", UnexpectedStar { offset: 6 }),
+            ("bytes  * 9/20", UnexpectedStar { offset: 7 }),
+            ("bytes 0-*/20", UnexpectedStar { offset: 8 }),
+            ("bytes -2/3", ExpectedNumber { offset: 6 }),
+            ("bytes 1-a/3", ExpectedNumber { offset: 8 }),
+            ("bytes 1-2/a", ExpectedNumber { offset: 10 }),
+            (
+                "bytes 0x01-0x02/3",
+                HexNotAllowed {
+                    field: Field::FirstByte,
+                },
+            ),
+            (
+                "bytes 1-0X02/3",
+                HexNotAllowed {
+                    field: Field::LastByte,
+                },
+            ),
+            (
+                "bytes */0x14",
+                HexNotAllowed {
+                    field: Field::CompleteLength,
+                },
+            ),
+            ("bytes 1-2/10x14", UnexpectedByte { offset: 12 }),
+            (
+                "bytes 1-2/99999999999999999999",
+                NumberOverflow { offset: 10 },
+            ),
+            (
+                "bytes 1-2/1234567890123456789012345",
+                NumberTooLong {
+                    field: Field::CompleteLength,
+                    digits: 25,
+                },
+            ),
+            (
+                "bytes 111111111111111111111-2/3",
+                NumberTooLong {
+                    field: Field::FirstByte,
+                    digits: 21,
+                },
+            ),
+            ("bytes 1-0/20", FirstAfterLast),
+            ("bytes 1-20/20", LastBeyondLength),
+        ] {
+            assert_eq!(ContentRange::try_parse(header), Err(expected), "{header}");
+            assert_eq!(ContentRange::parse(header), None);
+        }
+        assert_eq!(
+            ContentRange::try_parse("bytes 0-9/20").ok(),
+            bytes(0, 9, 20)
+        );
+    }
+
+    #[test]
+    fn test_parse_or_else() {
+        let mut errors = Vec::new();
+        let value = ContentRange::parse_or_else("bytes 0-9/20", |err| errors.push(err));
+        assert_eq!(value, bytes(0, 9, 20));
+        assert_eq!(errors, []);
+
+        let value = ContentRange::parse_or_else("bytes 1-a/3", |err| errors.push(err));
+        assert_eq!(value, None);
+        assert_eq!(errors, [ParseError::ExpectedNumber { offset: 8 }]);
+    }
+
+    #[test]
+    fn test_parse_all_reporting() {
+        assert_eq!(
+            ContentRange::parse_all_reporting(&[
+                "bytes 0-9/20",
+                "bytes 1-a/3",
+                "bytes */20",
+                "foo 1-2/3",
+                "bytes 0-9/*",
+            ]),
+            [
+                Ok(bytes(0, 9, 20).unwrap()),
+                Err(ParseError::ExpectedNumber { offset: 8 }),
+                Ok(unsatisfied(20).unwrap()),
+                Err(ParseError::UnknownUnit),
+                Ok(unbound(0, 9).unwrap()),
+            ]
+        );
+        assert_eq!(ContentRange::parse_all_reporting(&[]), []);
+    }
+
+    #[test]
+    fn test_from_kind_and_parts() {
+        use ContentRangeKind::{Bytes, UnboundBytes, Unsatisfied};
+        let missing = |field| Err(ParseError::MissingField { field });
+        let unexpected = |field| Err(ParseError::UnexpectedField { field });
+        for (kind, first, last, complete, expected) in [
+            (
+                Bytes,
+                Some(0),
+                Some(9),
+                Some(20),
+                Ok(bytes(0, 9, 20).unwrap()),
+            ),
+            (
+                UnboundBytes,
+                Some(0),
+                Some(9),
+                None,
+                Ok(unbound(0, 9).unwrap()),
+            ),
+            (
+                Unsatisfied,
+                None,
+                None,
+                Some(20),
+                Ok(unsatisfied(20).unwrap()),
+            ),
+            (
+                Bytes,
+                Some(0),
+                Some(9),
+                None,
+                missing(Field::CompleteLength),
+            ),
+            (Bytes, None, Some(9), Some(20), missing(Field::FirstByte)),
+            (UnboundBytes, Some(0), None, None, missing(Field::LastByte)),
+            (
+                UnboundBytes,
+                Some(0),
+                Some(9),
+                Some(20),
+                unexpected(Field::CompleteLength),
+            ),
+            (
+                Unsatisfied,
+                Some(0),
+                None,
+                Some(20),
+                unexpected(Field::FirstByte),
+            ),
+            (
+                Unsatisfied,
+                None,
+                None,
+                None,
+                missing(Field::CompleteLength),
+            ),
+            (
+                Bytes,
+                Some(9),
+                Some(0),
+                Some(20),
+                Err(ParseError::FirstAfterLast),
+            ),
+            (
+                Bytes,
+                Some(0),
+                Some(20),
+                Some(20),
+                Err(ParseError::LastBeyondLength),
+            ),
+            (
+                UnboundBytes,
+                Some(9),
+                Some(0),
+                None,
+                Err(ParseError::FirstAfterLast),
+            ),
+        ] {
+            assert_eq!(
+                ContentRange::from_kind_and_parts(kind, first, last, complete),
+                expected,
+                "{kind:?} {first:?} {last:?} {complete:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_with_code() {
+        for (header, expected, code) in [
+            ("bytes 0-9/20", bytes(0, 9, 20), "ok"),
+            ("bytes */20", unsatisfied(20), "ok"),
+            ("foo 0-9/20", None, "no_prefix"),
+            ("bytes 0-9", None, "unexpected_end"),
+            ("bytes 0-9/20 x", None, "unexpected_byte"),
+            ("bytes 0-a/20", None, "bad_number"),
+            ("bytes 0-9/99999999999999999999", None, "number_overflow"),
+            ("bytes 0-9/123456789012345678901", None, "number_too_long"),
+            ("bytes 0x1-9/20", None, "hex_number"),
+            ("bytes *-9/20", None, "unexpected_star"),
+            ("bytes 9-0/20", None, "first_gt_last"),
+            ("bytes 0-20/20", None, "last_ge_length"),
+        ] {
+            assert_eq!(
+                ContentRange::parse_with_code(header.as_bytes()),
+                (expected, code),
+                "{header}"
+            );
+        }
+        for (error, code) in [
+            (
+                ParseError::MissingField {
+                    field: Field::FirstByte,
+                },
+                "missing_field",
+            ),
+            (
+                ParseError::UnexpectedField {
+                    field: Field::FirstByte,
+                },
+                "unexpected_field",
+            ),
+            (ParseError::NumberAboveMax { offset: 0 }, "number_above_max"),
+        ] {
+            assert_eq!(error.code(), code);
+        }
+    }
+
+    #[test]
+    fn test_try_parse_verbose() {
+        for (header, context) in [
+            ("bytes 0-9/20 1", "/20 1"),
+            ("bytes 1-a/3", "s 1-a/3"),
+            ("bytes 1-2/a", "1-2/a"),
+            ("bytes 12345678/20", "5678/20"),
+            ("bytes 0-9/20x", "9/20x"),
+            ("bytes 1-20/20", "0/20"),
+            ("bytes 1-\u{e9}/20", "s 1-\u{e9}/2"),
+        ] {
+            let err = ContentRange::try_parse_verbose(header.as_bytes()).unwrap_err();
+            assert_eq!(err.context, context, "{header}");
+            assert_eq!(err.error, ContentRange::try_parse(header).unwrap_err());
+        }
+    }
+
+    #[test]
+    fn test_max_value() {
+        let options = ParseOptions {
+            max_value: Some(1000),
+            ..ParseOptions::default()
+        };
+        for (header, expected) in [
+            ("bytes 0-9/1000", bytes(0, 9, 1000)),
+            ("bytes 0-999/*", unbound(0, 999)),
+            ("bytes */1000", unsatisfied(1000)),
+            ("bytes 0-9/1001", None),
+            ("bytes 0-1001/*", None),
+            ("bytes 1001-1002/*", None),
+            ("bytes */1001", None),
+        ] {
+            assert_eq!(ContentRange::parse_with_options(header, &options), expected);
+        }
+        assert_eq!(
+            ContentRange::try_parse_with_spans(b"bytes 0-9/1001", &options).unwrap_err(),
+            ParseError::NumberAboveMax { offset: 10 }
+        );
+        assert_eq!(ContentRange::parse("bytes 0-9/1001"), bytes(0, 9, 1001));
+    }
+
+    #[test]
+    fn test_thousands_separators() {
+        let options = ParseOptions {
+            allow_thousands_separators: true,
+            ..ParseOptions::default()
+        };
+        for (header, expected) in [
+            ("bytes 0-9/1,000", bytes(0, 9, 1000)),
+            ("bytes 1_000-1,999/1,000,000", bytes(1000, 1999, 1_000_000)),
+            ("bytes 0-9/12_345_678", bytes(0, 9, 12_345_678)),
+            ("bytes 0-9/123,456", bytes(0, 9, 123_456)),
+            ("bytes */1,000", unsatisfied(1000)),
+            ("bytes 0-9/1,0,0,0", None),
+            ("bytes 0-9/1_000,000", None),
+            ("bytes 0-9/1,000_000", None),
+            ("bytes 0-9/1,00", None),
+            ("bytes 0-9/1,0000", None),
+            ("bytes 0-9/1000,000", None),
+            ("bytes 0-9/1,000,00", None),
+            ("bytes 0-9/,1000", None),
+            ("bytes 0-9/1000,", None),
+            ("bytes 0-9/1,,000", None),
+            ("bytes 0-9/1, 000", None),
+        ] {
+            assert_eq!(ContentRange::parse_with_options(header, &options), expected);
+        }
+        assert_eq!(ContentRange::parse("bytes 0-9/1,000"), None);
+        assert_eq!(ContentRange::parse("bytes 0-9/1_000"), None);
+    }
+
+    #[test]
+    fn test_parse_cow() {
+        assert_eq!(ContentRange::parse_cow("bytes 0-9/20"), bytes(0, 9, 20));
+        assert_eq!(
+            ContentRange::parse_cow(String::from("bytes 0-9/*")),
+            unbound(0, 9)
+        );
+        let header: Cow<'_, str> = Cow::Owned(String::from("bytes */20"));
+        assert_eq!(ContentRange::parse_cow(header), unsatisfied(20));
+        let header: Cow<'_, str> = Cow::Borrowed("bytes 0-9/x");
+        assert_eq!(ContentRange::parse_cow(header), None);
+    }
+
+    #[test]
+    fn test_whitespace_around_separators() {
+        let strict = ParseOptions {
+            allow_whitespace_around_separators: false,
+            ..ParseOptions::default()
+        };
+        for (header, lenient, expected) in [
+            ("bytes 0-9/20", bytes(0, 9, 20), bytes(0, 9, 20)),
+            ("bytes   0-9/20  ", bytes(0, 9, 20), bytes(0, 9, 20)),
+            ("bytes */20", unsatisfied(20), unsatisfied(20)),
+            ("bytes 0-9/*", unbound(0, 9), unbound(0, 9)),
+            ("bytes 0 -9/20", bytes(0, 9, 20), None),
+            ("bytes 0- 9/20", bytes(0, 9, 20), None),
+            ("bytes 0-9 /20", bytes(0, 9, 20), None),
+            ("bytes 0-9/\t20", bytes(0, 9, 20), None),
+            ("bytes 0-9/ *", unbound(0, 9), None),
+            ("bytes * /20", unsatisfied(20), None),
+            ("bytes */ 20", unsatisfied(20), None),
+        ] {
+            assert_eq!(ContentRange::parse(header), lenient);
+            assert_eq!(ContentRange::parse_with_options(header, &strict), expected);
+        }
+    }
+
+    #[test]
+    fn test_leading_plus() {
+        let options = ParseOptions {
+            allow_leading_plus: true,
+            ..ParseOptions::default()
+        };
+        for (header, expected) in [
+            ("bytes +0-+9/+20", bytes(0, 9, 20)),
+            ("bytes 0-+9/20", bytes(0, 9, 20)),
+            ("bytes +0-9/*", unbound(0, 9)),
+            ("bytes */+20", unsatisfied(20)),
+            ("bytes ++0-9/20", None),
+            ("bytes + 0-9/20", None),
+            ("bytes +-9/20", None),
+            ("bytes -0-9/20", None),
+        ] {
+            assert_eq!(ContentRange::parse_with_options(header, &options), expected);
+        }
+        assert_eq!(ContentRange::parse("bytes +0-+9/+20"), None);
+    }
+
+    #[test]
+    fn test_max_trailing_whitespace() {
+        let options = ParseOptions {
+            max_trailing_whitespace: Some(2),
+            ..ParseOptions::default()
+        };
+        for (header, expected) in [
+            ("bytes 0-9/20", Ok(bytes(0, 9, 20).unwrap())),
+            ("bytes 0-9/20 \t", Ok(bytes(0, 9, 20).unwrap())),
+            ("bytes 0-9/* \t", Ok(unbound(0, 9).unwrap())),
+            ("bytes */20  ", Ok(unsatisfied(20).unwrap())),
+            (
+                "bytes 0-9/20 \t ",
+                Err(ParseError::UnexpectedByte { offset: 14 }),
+            ),
+            (
+                "bytes */20   ",
+                Err(ParseError::UnexpectedByte { offset: 12 }),
+            ),
+        ] {
+            assert_eq!(
+                ContentRange::try_parse_with_spans(header.as_bytes(), &options).map(|(v, _)| v),
+                expected
+            );
+        }
+        assert_eq!(ContentRange::parse("bytes 0-9/20        "), bytes(0, 9, 20));
+    }
+
+    #[test]
+    fn test_only_decimal_digits() {
+        // every combination of the tolerance options, except for `radix` which opts out
+        // of decimal digits by design, and is checked separately below
+        for flags in 0..1024 {
+            let flag = |bit: u32| flags & (1 << bit) != 0;
+            let options = ParseOptions {
+                allow_thousands_separators: flag(0),
+                allow_whitespace_around_separators: flag(1),
+                allow_leading_plus: flag(2),
+                max_value: if flag(3) { Some(u64::MAX) } else { None },
+                max_trailing_whitespace: if flag(4) { Some(0) } else { None },
+                unit_aliases: if flag(5) {
+                    &[(b"octets", b"bytes")]
+                } else {
+                    &[]
+                },
+                max_digits: if flag(6) { Some(20) } else { None },
+                strip_utf8_bom: flag(7),
+                strip_trailing_crlf: flag(8),
+                whitespace: if flag(9) {
+                    |c: u8| c.is_ascii_whitespace()
+                } else {
+                    is_whitespace
+                },
+                ..ParseOptions::default()
+            };
+            for header in [
+                "bytes 0-9/0x14",
+                "bytes 0x0-9/20",
+                "bytes 0-0x9/20",
+                "bytes */0x14",
+                "bytes 0-9/1e2",
+                "bytes 0-9/-20",
+                "bytes 0-9/２０",
+                "bytes 0-9/0X14",
+                "bytes 0-9/1_0x14",
+                "bytes +0x0-9/20",
+                "octets 0-9/0x14",
+                "\u{feff}bytes 0-9/0x14",
+                "bytes 0-9/0x14\r\n",
+            ] {
+                assert_eq!(
+                    ContentRange::parse_with_options(header, &options),
+                    None,
+                    "{header} {options:?}"
+                );
+            }
+        }
+
+        // hex digits are allowed in radix 16, but not the `0x` prefix
+        let options = ParseOptions {
+            radix: 16,
+            ..ParseOptions::default()
+        };
+        for header in ["bytes 0-9/0x14", "bytes 0x0-9/20", "bytes */0X14"] {
+            assert_eq!(ContentRange::parse_with_options(header, &options), None);
+        }
+    }
+
+    #[test]
+    fn test_parse_advancing() {
+        for (header, expected, rest) in [
+            (&b"bytes 0-9/20"[..], bytes(0, 9, 20), &b""[..]),
+            (b"bytes 0-9/20\r\nnext", bytes(0, 9, 20), b"\r\nnext"),
+            (b"bytes 0 - 9 / 20  ", bytes(0, 9, 20), b"  "),
+            (b"bytes 0-9/*,bytes", unbound(0, 9), b",bytes"),
+            (b"bytes */20abc", unsatisfied(20), b"abc"),
+        ] {
+            let mut input = header;
+            assert_eq!(ContentRange::parse_advancing(&mut input), expected);
+            assert_eq!(input, rest);
+        }
+        for header in [&b"bytes 0-9/"[..], b"bytes 9-0/20 rest", b"foo 0-9/20", b""] {
+            let mut input = header;
+            assert_eq!(ContentRange::parse_advancing(&mut input), None);
+            assert_eq!(input, header);
+        }
+    }
+
+    #[test]
+    fn test_max_digits() {
+        let options = ParseOptions {
+            max_digits: Some(3),
+            ..ParseOptions::default()
+        };
+        for (header, expected) in [
+            (
+                "bytes 0-999/1000",
+                Err(ParseError::NumberTooLong {
+                    field: Field::CompleteLength,
+                    digits: 4,
+                }),
+            ),
+            ("bytes 0-999/*", Ok(unbound(0, 999).unwrap())),
+            (
+                "bytes 0000-9/20",
+                Err(ParseError::NumberTooLong {
+                    field: Field::FirstByte,
+                    digits: 4,
+                }),
+            ),
+            ("bytes 000-9/20", Ok(bytes(0, 9, 20).unwrap())),
+        ] {
+            let result =
+                ContentRange::try_parse_with_spans(header.as_bytes(), &options).map(|(v, _)| v);
+            assert_eq!(result, expected, "{header}");
+        }
+    }
+
+    #[test]
+    fn test_parse_dos_safe() {
+        let max = "bytes 18446744073709551613-18446744073709551613/18446744073709551614";
+        assert_eq!(
+            ContentRange::parse_dos_safe(max.as_bytes()),
+            bytes(u64::MAX - 2, u64::MAX - 2, u64::MAX - 1)
+        );
+        let padded = format!("bytes {:>20}-9/20", 0);
+        assert_eq!(
+            ContentRange::parse_dos_safe(padded.as_bytes()),
+            bytes(0, 9, 20)
+        );
+
+        let long_field = format!("bytes {:0>21}-9/20", 0);
+        assert_eq!(ContentRange::parse(&long_field), bytes(0, 9, 20));
+        assert_eq!(ContentRange::parse_dos_safe(long_field.as_bytes()), None);
+
+        let long_header = format!("bytes 0-9/20{:129}", "");
+        assert_eq!(ContentRange::parse(&long_header), bytes(0, 9, 20));
+        assert_eq!(ContentRange::parse_dos_safe(long_header.as_bytes()), None);
+
+        let long_spacing = format!("bytes 0{:100}-9/20", "");
+        assert_eq!(
+            ContentRange::parse_dos_safe(long_spacing.as_bytes()),
+            bytes(0, 9, 20)
+        );
+        let long_trailing = format!("bytes 0-9/20{:17}", "");
+        assert_eq!(ContentRange::parse_dos_safe(long_trailing.as_bytes()), None);
+    }
+
+    #[test]
+    fn test_parse_maybe_incomplete() {
+        use ParseProgress::{Complete, Incomplete, Invalid};
+        for (header, expected) in [
+            ("bytes 0-9/20", Complete(bytes(0, 9, 20).unwrap())),
+            ("bytes 0-9/* ", Complete(unbound(0, 9).unwrap())),
+            ("bytes */20", Complete(unsatisfied(20).unwrap())),
+            ("", Incomplete),
+            ("byt", Incomplete),
+            ("bytes", Incomplete),
+            ("bytes ", Incomplete),
+            ("bytes 0", Incomplete),
+            ("bytes 0-", Incomplete),
+            ("bytes 0-9 /", Incomplete),
+            ("bytes 0-9/2", Incomplete),
+            ("bytes 10-1", Incomplete),
+            ("bytes *", Incomplete),
+            ("bytes 0-a", Invalid),
+            ("bytes 10-1/20", Invalid),
+            ("bytes 0-9/2 ", Invalid),
+            ("bytes 0-9/20 x", Invalid),
+            ("bytes0", Invalid),
+            ("items 0-9/20", Invalid),
+        ] {
+            assert_eq!(
+                ContentRange::parse_maybe_incomplete(header.as_bytes()),
+                expected,
+                "{header}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_exact() {
+        for (header, expected_len, expected) in [
+            (&b"bytes 0-9/20"[..], 12, bytes(0, 9, 20)),
+            (b"bytes 0-9/20  ", 12, bytes(0, 9, 20)),
+            (b"bytes 0-9/20  ", 14, None),
+            (b"bytes 0-9/20", 13, None),
+            (b"bytes 0-9/20", 11, None),
+            (b"bytes 0-9/200", 12, None),
+            (b"bytes 0-9/200", 13, bytes(0, 9, 200)),
+            (b"bytes 0-9/*", 11, unbound(0, 9)),
+            (b"bytes 0-9/*", 10, None),
+            (b"bytes 0-9/20\0\0\0", 12, bytes(0, 9, 20)),
+            (b"bytes 0-9/20\0\0\0", 13, None),
+            (b"bytes */20bytes 0-9/*", 10, unsatisfied(20)),
+            (b"", 0, None),
+        ] {
+            assert_eq!(ContentRange::parse_exact(header, expected_len), expected);
+        }
+    }
+
+    #[test]
+    fn test_unit_aliases() {
+        let options = ParseOptions {
+            unit_aliases: &[(b"byte", b"bytes"), (b"octets", b"bytes")],
+            ..ParseOptions::default()
+        };
+        for (header, expected) in [
+            ("byte 0-9/20", bytes(0, 9, 20)),
+            ("octets 0-9/*", unbound(0, 9)),
+            ("octets */20", unsatisfied(20)),
+            ("bytes 0-9/20", bytes(0, 9, 20)),
+            ("bits 0-9/20", None),
+            ("byte0-9/20", None),
+        ] {
+            assert_eq!(
+                ContentRange::parse_with_options(header, &options),
+                expected,
+                "{header}"
+            );
+        }
+        assert_eq!(ContentRange::parse("byte 0-9/20"), None);
+        assert_eq!(ContentRange::parse("octets 0-9/20"), None);
+
+        // aliases of other units are still rejected
+        let options = ParseOptions {
+            unit_aliases: &[(b"rows", b"items")],
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            ContentRange::parse_with_options("rows 0-9/20", &options),
+            None
+        );
+    }
+
+    #[test]
+    fn test_strip_utf8_bom() {
+        let options = ParseOptions {
+            strip_utf8_bom: true,
+            ..ParseOptions::default()
+        };
+        for (header, expected) in [
+            (&b"\xEF\xBB\xBFbytes 0-9/20"[..], bytes(0, 9, 20)),
+            (b"\xEF\xBB\xBFbytes */20", unsatisfied(20)),
+            (b"bytes 0-9/20", bytes(0, 9, 20)),
+            (b"\xEF\xBB\xBF\xEF\xBB\xBFbytes 0-9/20", None),
+            (b"\xEF\xBBbytes 0-9/20", None),
+            (b" \xEF\xBB\xBFbytes 0-9/20", None),
+        ] {
+            assert_eq!(
+                ContentRange::parse_bytes_with_options(header, &options),
+                expected
+            );
+        }
+        assert_eq!(ContentRange::parse_bytes(b"\xEF\xBB\xBFbytes 0-9/20"), None);
+
+        let header = "\u{feff}bytes 42-69/420";
+        let spans = ContentRange::try_parse_with_spans(header.as_bytes(), &options)
+            .unwrap()
+            .1;
+        assert_eq!(&header[spans.unit], "bytes");
+        assert_eq!(spans.first_byte.map(|span| &header[span]), Some("42"));
+        assert_eq!(
+            ContentRange::try_parse_with_spans(b"\xEF\xBB\xBFbytes 1-a/3", &options),
+            Err(ParseError::ExpectedNumber { offset: 11 })
+        );
+    }
+
+    #[test]
+    fn test_strip_trailing_crlf() {
+        let options = ParseOptions {
+            strip_trailing_crlf: true,
+            ..ParseOptions::default()
+        };
+        for (header, expected) in [
+            ("bytes 0-9/20\r\n", bytes(0, 9, 20)),
+            ("bytes 0-9/20\n", bytes(0, 9, 20)),
+            ("bytes 0-9/* \r\n", unbound(0, 9)),
+            ("bytes */20", unsatisfied(20)),
+            ("bytes 0-9/20\r", None),
+            ("bytes 0-9/20\r\n\r\n", None),
+            ("bytes 0-9/20\r\n ", None),
+        ] {
+            assert_eq!(
+                ContentRange::parse_with_options(header, &options),
+                expected,
+                "{header:?}"
+            );
+        }
+        assert_eq!(ContentRange::parse("bytes 0-9/20\r\n"), None);
+        assert_eq!(ContentRange::parse("bytes 0-9/20\n"), None);
+    }
+
+    #[test]
+    fn test_radix() {
+        let hex = ParseOptions {
+            radix: 16,
+            ..ParseOptions::default()
+        };
+        for (header, expected) in [
+            ("bytes a-f/10", bytes(10, 15, 16)),
+            ("bytes A-F/10", bytes(10, 15, 16)),
+            ("bytes 0-ff/*", unbound(0, 255)),
+            ("bytes */ffffffffffffffff", unsatisfied(u64::MAX)),
+            ("bytes */10000000000000000", None),
+            ("bytes 0x1-f/10", None),
+            ("bytes a-g/10", None),
+        ] {
+            assert_eq!(
+                ContentRange::parse_with_options(header, &hex),
+                expected,
+                "{header}"
+            );
+        }
+        assert_eq!(ContentRange::parse("bytes a-f/10"), None);
+
+        let binary = ParseOptions {
+            radix: 2,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            ContentRange::parse_with_options("bytes 10-11/100", &binary),
+            bytes(2, 3, 4)
+        );
+        assert_eq!(
+            ContentRange::parse_with_options("bytes 1-2/100", &binary),
+            None
+        );
+
+        // numbers longer than any `u64` value in the radix
+        let too_long = |options: &ParseOptions<'_>, header: &str| {
+            ContentRange::try_parse_with_spans(header.as_bytes(), options).map(|(v, _)| v)
+        };
+        assert_eq!(
+            too_long(&hex, "bytes */10000000000000000"),
+            Err(ParseError::NumberTooLong {
+                field: Field::CompleteLength,
+                digits: 17
+            })
+        );
+        assert_eq!(
+            too_long(&hex, "bytes */1ffffffffffffffff"),
+            Err(ParseError::NumberTooLong {
+                field: Field::CompleteLength,
+                digits: 17
+            })
+        );
+        let max = "1".repeat(64);
+        assert_eq!(
+            ContentRange::parse_with_options(&format!("bytes */{max}"), &binary),
+            unsatisfied(u64::MAX)
+        );
+        assert_eq!(
+            too_long(&binary, &format!("bytes */1{max}")),
+            Err(ParseError::NumberTooLong {
+                field: Field::CompleteLength,
+                digits: 65
+            })
+        );
+        assert_eq!(
+            too_long(&binary, &format!("bytes */{}", "1".repeat(21))),
+            Ok(unsatisfied((1 << 21) - 1).unwrap())
+        );
+
+        for radix in [0, 1, 17, 36, 100] {
+            let options = ParseOptions {
+                radix,
+                ..ParseOptions::default()
+            };
+            for header in ["bytes 0-1/10", "bytes */10"] {
+                assert_eq!(
+                    ContentRange::try_parse_with_spans(header.as_bytes(), &options).map(|(v, _)| v),
+                    Err(ParseError::UnsupportedRadix { radix }),
+                    "{header}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_whitespace() {
+        let options = ParseOptions {
+            whitespace: |c| c == b' ' || c == b'\t' || c == b'\x0c',
+            ..ParseOptions::default()
+        };
+        for (header, expected) in [
+            ("bytes\x0c0-9/20", bytes(0, 9, 20)),
+            ("bytes 0\x0c-\x0c9\x0c/\x0c20\x0c", bytes(0, 9, 20)),
+            ("bytes\x0c*/20", unsatisfied(20)),
+            ("bytes 0-9/20", bytes(0, 9, 20)),
+            ("bytes\x0b0-9/20", None),
+        ] {
+            assert_eq!(
+                ContentRange::parse_with_options(header, &options),
+                expected,
+                "{header:?}"
+            );
+        }
+        assert_eq!(ContentRange::parse("bytes\x0c0-9/20"), None);
+
+        let options = ParseOptions {
+            whitespace: |c| c == b'_',
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            ContentRange::parse_with_options("bytes_0_-_9/20__", &options),
+            bytes(0, 9, 20)
+        );
+        assert_eq!(
+            ContentRange::parse_with_options("bytes 0-9/20", &options),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_ascii() {
+        for header in [
+            &b"byt\xE9s 0-9/20"[..],
+            b"bytes 0-\xB99/20",
+            b"bytes 0-9/20\x80",
+            b"bytes 0-9/20 \xFF",
+        ] {
+            assert_eq!(ContentRange::parse_ascii(header), None);
+        }
+    }
+
+    #[test]
+    fn test_parse_with_params() {
+        for (header, expected, expected_params) in [
+            ("bytes 0-9/20", bytes(0, 9, 20), None),
+            ("bytes 0-9/20;foo=bar", bytes(0, 9, 20), Some("foo=bar")),
+            ("bytes 0-9/* ; a=1; b", unbound(0, 9), Some(" a=1; b")),
+            ("bytes */20;", unsatisfied(20), Some("")),
+            ("bytes 0-9;foo=bar", None, Some("foo=bar")),
+        ] {
+            assert_eq!(
+                ContentRange::parse_with_params(header),
+                (expected, expected_params)
+            );
+        }
+        assert_eq!(ContentRange::parse("bytes 0-9/20;foo=bar"), None);
+    }
+
+    #[test]
+    fn test_detected_unit() {
+        for (header, expected) in [
+            ("bytes 0-9/20", DetectedUnit::Bytes),
+            ("bytes\t*/20", DetectedUnit::Bytes),
+            ("bytes foo", DetectedUnit::Bytes),
+            ("items 0-9/20", DetectedUnit::Other("items")),
+            ("Bytes 0-9/20", DetectedUnit::Other("Bytes")),
+            ("my-unit.v2 0-9/20", DetectedUnit::Other("my-unit.v2")),
+            (
+                "x!#$%&'*+^_`|~ 0-9/20",
+                DetectedUnit::Other("x!#$%&'*+^_`|~"),
+            ),
+            ("my/unit 0-9/20", DetectedUnit::None),
+            ("my,unit 0-9/20", DetectedUnit::None),
+            ("b\u{e9}tes 0-9/20", DetectedUnit::None),
+            ("bytes=0-9 foo", DetectedUnit::None),
+            ("bytes=0-9", DetectedUnit::None),
+            ("0-9/20", DetectedUnit::None),
+            (" bytes 0-9/20", DetectedUnit::None),
+            ("", DetectedUnit::None),
+        ] {
+            assert_eq!(ContentRange::detected_unit(header), expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_detailed() {
+        for (header, expected, unit) in [
+            ("bytes 0-9/20", bytes(0, 9, 20), Some("bytes")),
+            ("bytes\t*/20", unsatisfied(20), Some("bytes")),
+            ("bytes 0-9/x", None, Some("bytes")),
+            ("items 0-9/20", None, Some("items")),
+            ("0-9/20", None, None),
+        ] {
+            assert_eq!(ContentRange::parse_detailed(header), (expected, unit));
+        }
+    }
+
+    #[test]
+    fn test_parse_from_reader() {
+        let mut reader = Cursor::new(&b"bytes 0-9/20\nbytes */30\r\nbytes 0-9/"[..]);
+        assert_eq!(
+            ContentRange::parse_from_reader(&mut reader, 100).unwrap(),
+            bytes(0, 9, 20)
+        );
+        assert_eq!(
+            ContentRange::parse_from_reader(&mut reader, 100).unwrap(),
+            unsatisfied(30)
+        );
+        assert_eq!(
+            ContentRange::parse_from_reader(&mut reader, 100).unwrap(),
+            None
+        );
+
+        // the value must end within `max` bytes
+        let mut reader = Cursor::new(&b"bytes */205"[..]);
+        let err = ContentRange::parse_from_reader(&mut reader, 10).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(reader.position(), 10);
+        let mut reader = Cursor::new(&b"bytes */205\nbytes */20"[..]);
+        let err = ContentRange::parse_from_reader(&mut reader, 11).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // exactly `max` bytes followed by the end of input or a newline
+        let mut reader = Cursor::new(&b"bytes */205"[..]);
+        assert_eq!(
+            ContentRange::parse_from_reader(&mut reader, 11).unwrap(),
+            unsatisfied(205)
+        );
+        let mut reader = Cursor::new(&b"bytes */205\nbytes */20"[..]);
+        assert_eq!(
+            ContentRange::parse_from_reader(&mut reader, 12).unwrap(),
+            unsatisfied(205)
+        );
+        assert_eq!(reader.position(), 12);
+    }
+
+    #[test]
+    fn test_parse_header_line() {
+        for (line, expected) in [
+            ("Content-Range: bytes 0-9/20", bytes(0, 9, 20)),
+            ("content-range:bytes 0-9/20", bytes(0, 9, 20)),
+            ("CONTENT-RANGE: \t  bytes */20 \t", unsatisfied(20)),
+            ("Content-Range: bytes 0-9/30", bytes(0, 9, 30)),
+            ("Content-Length: 20", None),
+            ("Content-Range-X: bytes 0-9/20", None),
+            ("Content-Range bytes 0-9/20", None),
+            ("Content-Range : bytes 0-9/20", None),
+            ("Content-Range: foo", None),
+        ] {
+            assert_eq!(ContentRange::parse_header_line(line), expected);
+        }
+        for name in [CONTENT_RANGE, CONTENT_RANGE_LOWER] {
+            let line = format!("{name}: bytes 0-9/20");
+            assert_eq!(ContentRange::parse_header_line(&line), bytes(0, 9, 20));
+        }
+    }
+
+    #[test]
+    fn test_merge_knowledge() {
+        for (left, right, expected) in [
+            ("bytes 0-9/*", "bytes */20", bytes(0, 9, 20)),
+            ("bytes */20", "bytes 0-9/*", bytes(0, 9, 20)),
+            ("bytes 0-9/20", "bytes */20", bytes(0, 9, 20)),
+            ("bytes 0-9/20", "bytes 10-19/*", bytes(0, 9, 20)),
+            ("bytes 0-9/*", "bytes 5-7/*", unbound(0, 9)),
+            ("bytes */20", "bytes */20", unsatisfied(20)),
+            // conflicting complete_length
+            ("bytes 0-9/20", "bytes */30", None),
+            ("bytes */20", "bytes */30", None),
+            // range does not fit into the known length
+            ("bytes 0-9/*", "bytes */5", None),
+            ("bytes 0-9/20", "bytes 30-39/*", None),
+        ] {
+            let left = ContentRange::parse(left).unwrap();
+            let right = ContentRange::parse(right).unwrap();
+            assert_eq!(left.merge_knowledge(&right), expected);
+        }
+    }
+
+    #[test]
+    fn test_to_triple() {
+        assert_eq!(bytes(5, 9, 20).unwrap().to_triple(), (5, 9, 20));
+        assert_eq!(unbound(5, 9).unwrap().to_triple(), (5, 9, 0));
+        assert_eq!(unsatisfied(20).unwrap().to_triple(), (0, 0, 20));
+    }
+
+    #[test]
+    fn test_map_offsets() {
+        for value in [bytes(5, 9, 20), unbound(5, 9), unsatisfied(20)] {
+            let value = value.unwrap();
+            assert_eq!(value.map_offsets(|v| v), Some(value));
+        }
+        let double = |v| v * 2;
+        assert_eq!(
+            bytes(5, 9, 20).unwrap().map_offsets(double),
+            bytes(10, 18, 40)
+        );
+        assert_eq!(unbound(5, 9).unwrap().map_offsets(double), unbound(10, 18));
+        assert_eq!(
+            unsatisfied(20).unwrap().map_offsets(double),
+            unsatisfied(40)
+        );
+
+        let reverse = |v| 100 - v;
+        assert_eq!(bytes(5, 9, 20).unwrap().map_offsets(reverse), None);
+        assert_eq!(unbound(5, 9).unwrap().map_offsets(reverse), None);
+        assert_eq!(bytes(5, 9, 20).unwrap().map_offsets(|v| v.min(9)), None);
+    }
+
+    #[test]
+    fn test_satisfies_request() {
+        for (header, req_first, req_last, expected) in [
+            ("bytes 10-19/50", 10, Some(19), true),
+            ("bytes 10-19/*", 10, Some(19), true),
+            ("bytes 10-49/50", 10, None, true),
+            ("bytes 10-19/*", 10, None, true),
+            ("bytes 0-19/50", 10, Some(19), false),
+            ("bytes 10-29/50", 10, Some(19), false),
+            ("bytes 0-49/50", 10, None, false),
+            ("bytes */50", 10, None, false),
+        ] {
+            let value = ContentRange::parse(header).unwrap();
+            assert_eq!(value.satisfies_request(req_first, req_last), expected);
+        }
+    }
+
+    #[test]
+    fn test_expect() {
+        let value = ContentRange::parse("bytes 0-9/20").unwrap().expect_bytes();
+        assert_eq!(Some(ContentRange::Bytes(value)), bytes(0, 9, 20));
+        let value = ContentRange::parse("bytes 0-9/*").unwrap().expect_unbound();
+        assert_eq!(Some(ContentRange::UnboundBytes(value)), unbound(0, 9));
+        let value = ContentRange::parse("bytes */20")
+            .unwrap()
+            .expect_unsatisfied();
+        assert_eq!(Some(ContentRange::Unsatisfied(value)), unsatisfied(20));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "expected ContentRange::Bytes, got ContentRange::Unsatisfied { * / 20 }"
+    )]
+    fn test_expect_bytes_wrong_variant() {
+        let _ = unsatisfied(20).unwrap().expect_bytes();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "expected ContentRange::UnboundBytes, got ContentRange::Bytes { 0..=9 / 20 }"
+    )]
+    fn test_expect_unbound_wrong_variant() {
+        let _ = bytes(0, 9, 20).unwrap().expect_unbound();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "expected ContentRange::Unsatisfied, got ContentRange::UnboundBytes { 0..=9 / * }"
+    )]
+    fn test_expect_unsatisfied_wrong_variant() {
+        let _ = unbound(0, 9).unwrap().expect_unsatisfied();
+    }
+
+    #[test]
+    fn test_try_into() {
+        let value = bytes(0, 9, 20).unwrap();
+        assert_eq!(value.try_into_bytes().map(ContentRange::Bytes), Ok(value));
+        assert_eq!(value.try_into_unbound(), Err(value));
+        assert_eq!(value.try_into_unsatisfied(), Err(value));
+
+        let value = unbound(0, 9).unwrap();
+        assert_eq!(
+            value.try_into_unbound().map(ContentRange::UnboundBytes),
+            Ok(value)
+        );
+        assert_eq!(value.try_into_bytes(), Err(value));
+        assert_eq!(value.try_into_unsatisfied(), Err(value));
+
+        let value = unsatisfied(20).unwrap();
+        assert_eq!(
+            value.try_into_unsatisfied().map(ContentRange::Unsatisfied),
+            Ok(value)
+        );
+        assert_eq!(value.try_into_bytes(), Err(value));
+        assert_eq!(value.try_into_unbound(), Err(value));
+    }
+
+    #[test]
+    fn test_is_satisfiable() {
+        assert!(bytes(0, 9, 20).unwrap().is_satisfiable());
+        assert!(unbound(0, 9).unwrap().is_satisfiable());
+        assert!(!unsatisfied(20).unwrap().is_satisfiable());
+    }
+
+    #[test]
+    fn test_is_empty_resource() {
+        for (header, expected) in [
+            ("bytes */0", Ok(true)),
+            ("bytes */1", Ok(false)),
+            ("bytes 0-0/1", Ok(false)),
+            ("bytes 0-0/*", Ok(false)),
+            ("bytes 0-0/0", Err(ParseError::LastBeyondLength)),
+        ] {
+            let result = ContentRange::try_parse(header).map(|v| v.is_empty_resource());
+            assert_eq!(result, expected, "{header}");
+        }
+    }
+
+    #[test]
+    fn test_unit_matches() {
+        for value in [bytes(0, 9, 20), unbound(0, 9), unsatisfied(20)] {
+            let value = value.unwrap();
+            assert!(value.unit_matches("bytes"));
+            assert!(value.unit_matches(" Bytes "));
+            assert!(!value.unit_matches("none"));
+            assert!(!value.unit_matches(""));
+        }
+    }
+
+    #[test]
+    fn test_matches_header_ignoring_whitespace() {
+        let value = bytes(0, 9, 20).unwrap();
+        for header in [
+            "bytes 0-9/20",
+            "bytes\t0-9/20",
+            "bytes 0 - 9 / 20",
+            "bytes \t 0\t-9/ 20 \t ",
+        ] {
+            assert!(value.matches_header_ignoring_whitespace(header), "{header}");
+        }
+        for header in [
+            "bytes 0-9/21",
+            "bytes 0-9/*",
+            "bytes0-9/20",
+            "bytes 0 9/20",
+            "",
+        ] {
+            assert!(
+                !value.matches_header_ignoring_whitespace(header),
+                "{header}"
+            );
+        }
+        let value = unsatisfied(20).unwrap();
+        assert!(value.matches_header_ignoring_whitespace("bytes  * / 20"));
+    }
+
+    #[test]
+    fn test_is_canonical() {
+        for (header, expected) in [
+            ("bytes 0-9/20", true),
+            ("bytes 0-9/*", true),
+            ("bytes */20", true),
+            ("bytes */0", true),
+            ("bytes  0-9/20", false),
+            ("bytes\t0-9/20", false),
+            ("bytes 0 -9/20", false),
+            ("bytes 0-9/20 ", false),
+            ("bytes 00-9/20", false),
+            ("bytes 0-09/20", false),
+            ("bytes */020", false),
+            ("bytes 0-9/ *", false),
+            ("Bytes 0-9/20", false),
+            ("bytes 9-0/20", false),
+            ("", false),
+        ] {
+            assert_eq!(ContentRange::is_canonical(header), expected, "{header}");
+        }
+    }
+
+    #[test]
+    fn test_len_hint() {
+        for (value, expected) in [
+            (bytes(10, 19, 20), (10, Some(10))),
+            (bytes(0, 0, 1), (1, Some(1))),
+            (unbound(10, 19), (10, Some(10))),
+            (unbound(0, u64::MAX - 1), (u64::MAX, Some(u64::MAX))),
+            (unbound(0, u64::MAX), (u64::MAX, None)),
+            (unsatisfied(20), (0, Some(0))),
+        ] {
+            assert_eq!(value.unwrap().len_hint(), expected);
+        }
+
+        // built by hand, bypassing validation
+        let malformed = ContentRange::UnboundBytes(ContentRangeUnbound {
+            first_byte: 10,
+            last_byte: 9,
+        });
+        assert_eq!(malformed.len_hint(), (0, None));
+    }
+
+    #[test]
+    fn test_complete_length() {
+        for (value, expected) in [
+            (bytes(0, 9, 20), Some(20)),
+            (unbound(0, 9), None),
+            (unsatisfied(20), Some(20)),
+        ] {
+            assert_eq!(value.unwrap().complete_length(), expected);
+            assert_eq!(value.unwrap().resource_key(), expected);
+        }
+    }
+
+    #[test]
+    fn test_byte_view() {
+        let view = |first_byte, last_byte, complete_length| {
+            Some(ByteView {
+                first_byte,
+                last_byte,
+                complete_length,
+            })
+        };
+        for (value, expected) in [
+            (bytes(0, 9, 20), view(0, 9, Some(20))),
+            (unbound(0, 9), view(0, 9, None)),
+            (unsatisfied(20), None),
+        ] {
+            assert_eq!(value.unwrap().byte_view(), expected);
+        }
+    }
+
+    #[test]
+    fn test_synthesize_content_range() {
+        for (req_first, req_last, complete_length, expected) in [
+            (0, Some(9), 20, bytes(0, 9, 20)),
+            (10, Some(19), 20, bytes(10, 19, 20)),
+            (10, Some(99), 20, bytes(10, 19, 20)),
+            (10, Some(u64::MAX), 20, bytes(10, 19, 20)),
+            (10, None, 20, bytes(10, 19, 20)),
+            (19, None, 20, bytes(19, 19, 20)),
+            (20, None, 20, unsatisfied(20)),
+            (20, Some(29), 20, unsatisfied(20)),
+            (0, None, 0, unsatisfied(0)),
+            (10, Some(5), 20, unsatisfied(20)),
+            // suffix requests, `bytes=-5` and `bytes=-30`
+            (20 - 5, None, 20, bytes(15, 19, 20)),
+            (20_u64.saturating_sub(30), None, 20, bytes(0, 19, 20)),
+        ] {
+            assert_eq!(
+                Some(synthesize_content_range(
+                    req_first,
+                    req_last,
+                    complete_length
+                )),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_touches_end() {
+        for (header, expected) in [
+            ("bytes 0-19/20", true),
+            ("bytes 19-19/20", true),
+            ("bytes 0-18/20", false),
+            ("bytes 0-19/*", false),
+            ("bytes */20", false),
+        ] {
+            assert_eq!(ContentRange::parse(header).unwrap().touches_end(), expected);
+        }
+    }
+
+    #[test]
+    fn test_with_fields() {
+        let r = ContentRangeBytes {
+            first_byte: 10,
+            last_byte: 19,
+            complete_length: 50,
+        };
+        let expected = |first_byte, last_byte, complete_length| {
+            Some(ContentRangeBytes {
+                first_byte,
+                last_byte,
+                complete_length,
+            })
+        };
+        assert_eq!(r.with_first_byte(0), expected(0, 19, 50));
+        assert_eq!(r.with_first_byte(19), expected(19, 19, 50));
+        assert_eq!(r.with_first_byte(20), None);
+        assert_eq!(r.with_last_byte(10), expected(10, 10, 50));
+        assert_eq!(r.with_last_byte(49), expected(10, 49, 50));
+        assert_eq!(r.with_last_byte(9), None);
+        assert_eq!(r.with_last_byte(50), None);
+        assert_eq!(r.with_complete_length(20), expected(10, 19, 20));
+        assert_eq!(r.with_complete_length(19), None);
+    }
+
+    #[test]
+    fn test_chunks() {
+        let range = |first_byte, last_byte| ContentRangeBytes {
+            first_byte,
+            last_byte,
+            complete_length: 20,
+        };
+        let chunks: Vec<_> = range(0, 9).chunks(4).collect();
+        assert_eq!(chunks, [range(0, 3), range(4, 7), range(8, 9)]);
+        let chunks: Vec<_> = range(5, 12).chunks(4).collect();
+        assert_eq!(chunks, [range(5, 8), range(9, 12)]);
+        let chunks: Vec<_> = range(5, 5).chunks(4).collect();
+        assert_eq!(chunks, [range(5, 5)]);
+        let chunks: Vec<_> = range(0, 9).chunks(u64::MAX).collect();
+        assert_eq!(chunks, [range(0, 9)]);
     }
 
-    fn unsatisfied(complete_length: u64) -> Option<ContentRange> {
-        Some(ContentRange::Unsatisfied(ContentRangeUnsatisfied {
-            complete_length,
-        }))
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn test_chunks_zero() {
+        let r = ContentRangeBytes {
+            first_byte: 0,
+            last_byte: 9,
+            complete_length: 20,
+        };
+        let _ = r.chunks(0);
     }
 
     #[test]
-    fn test_parse() {
-        for (header, expected) in vec![
-            // Valid
-            ("bytes 0-9/20", bytes(0, 9, 20)),
-            ("bytes\t 0 \t -\t \t  \t9 / 20   ", bytes(0, 9, 20)),
-            ("bytes */20", unsatisfied(20)),
-            ("bytes   *\t\t/  20    ", unsatisfied(20)),
-            ("bytes 0-9/*", unbound(0, 9)),
-            ("bytes   0  -    9  /  *   ", unbound(0, 9)),
-            //
-            // Errors
-            //
-            ("", None),
-            ("b", None),
-            ("foo", None),
-            ("foo 1-2/3", None),
-            (" bytes 1-2/3", None),
-            ("bytes -2/3", None),
-            ("bytes 1-/3", None),
-            ("bytes 1-2/", None),
-            ("bytes 1-2/a", None),
-            ("bytes1-2/3", None),
-            ("bytes=1-2/3", None),
-            ("bytes a-2/3", None),
-            ("bytes 1-a/3", None),
-            ("bytes 0x01-0x02/3", None),
-            ("bytes 1-2/a", None),
-            (
-                "bytes 1111111111111111111111111111111111111111111-2/1",
-                None,
-            ),
-            ("bytes 1-3/20 1", None),
-            ("bytes 1-3/* 1", None),
-            ("bytes */1 1", None),
-            ("bytes 1-0/20", None),
-            ("bytes 1-20/20", None),
-            ("bytes 1-21/20", None),
+    fn test_is_adjacent_to() {
+        let range = |first_byte, last_byte| ContentRangeBytes {
+            first_byte,
+            last_byte,
+            complete_length: u64::MAX,
+        };
+        for (a, b, expected) in [
+            (range(0, 9), range(10, 19), true),
+            (range(10, 19), range(0, 9), true),
+            (range(0, 9), range(9, 19), false),
+            (range(0, 9), range(5, 7), false),
+            (range(0, 9), range(11, 19), false),
+            (range(0, u64::MAX - 1), range(0, u64::MAX - 1), false),
         ] {
-            assert_eq!(ContentRange::parse(header), expected);
-            assert_eq!(ContentRange::try_from(header).ok(), expected);
-            assert_eq!(ContentRange::from_str(header).ok(), expected);
-            assert_eq!(ContentRange::try_from(header.as_bytes()).ok(), expected);
+            assert_eq!(a.is_adjacent_to(&b), expected);
+        }
+    }
+
+    #[test]
+    fn test_chunks_remaining() {
+        let r = ContentRangeBytes {
+            first_byte: 0,
+            last_byte: 9,
+            complete_length: 100,
+        };
+        for (chunk_size, expected) in [
+            (0, None),
+            (1, Some(90)),
+            (10, Some(9)),
+            (30, Some(3)),
+            (40, Some(3)),
+            (89, Some(2)),
+            (90, Some(1)),
+            (u64::MAX, Some(1)),
+        ] {
+            assert_eq!(r.chunks_remaining(chunk_size), expected);
+        }
+        let r = ContentRangeBytes {
+            first_byte: 90,
+            last_byte: 99,
+            complete_length: 100,
+        };
+        assert_eq!(r.chunks_remaining(10), Some(0));
+        assert_eq!(r.chunks_remaining(0), None);
+
+        // built by hand, bypassing validation
+        let r = ContentRangeBytes {
+            first_byte: 0,
+            last_byte: u64::MAX,
+            complete_length: 100,
+        };
+        assert_eq!(r.chunks_remaining(10), None);
+    }
+
+    #[test]
+    fn test_next_chunk() {
+        let next = |first_byte, last_byte, complete_length, chunk_size| {
+            ContentRangeBytes {
+                first_byte,
+                last_byte,
+                complete_length,
+            }
+            .next_chunk(chunk_size)
+            .map(|r| (r.first_byte, r.last_byte, r.complete_length))
+        };
+        assert_eq!(next(0, 9, 100, 10), Some((10, 19, 100)));
+        assert_eq!(next(10, 19, 100, 50), Some((20, 69, 100)));
+        assert_eq!(next(80, 89, 100, 20), Some((90, 99, 100)));
+        assert_eq!(next(0, 98, 100, 10), Some((99, 99, 100)));
+        assert_eq!(
+            next(0, 9, u64::MAX, u64::MAX),
+            Some((10, u64::MAX - 1, u64::MAX))
+        );
+        assert_eq!(next(90, 99, 100, 10), None);
+        assert_eq!(next(0, 9, 100, 0), None);
+        assert_eq!(next(0, 200, 100, 10), None);
+        assert_eq!(next(0, u64::MAX, u64::MAX, 10), None);
+    }
+
+    #[test]
+    fn test_to_next_range_request() {
+        let request = |first_byte, last_byte, complete_length, chunk_size| {
+            ContentRangeBytes {
+                first_byte,
+                last_byte,
+                complete_length,
+            }
+            .to_next_range_request(chunk_size)
+        };
+        assert_eq!(request(0, 9, 100, 10).as_deref(), Some("bytes=10-19"));
+        assert_eq!(request(0, 89, 100, 20).as_deref(), Some("bytes=90-99"));
+        assert_eq!(request(90, 99, 100, 10), None);
+        assert_eq!(request(0, 9, 100, 0), None);
+    }
+
+    #[test]
+    fn test_absolute_position() {
+        let r = ContentRangeBytes {
+            first_byte: 10,
+            last_byte: 19,
+            complete_length: 50,
+        };
+        assert_eq!(r.absolute_position(0), Some(10));
+        assert_eq!(r.absolute_position(9), Some(19));
+        assert_eq!(r.absolute_position(10), None);
+        assert_eq!(r.absolute_position(u64::MAX), None);
+    }
+
+    #[test]
+    fn test_overlaps_request() {
+        let r = ContentRangeBytes {
+            first_byte: 10,
+            last_byte: 19,
+            complete_length: 50,
+        };
+        for (req_first, req_last, expected) in [
+            (10, Some(19), true),
+            (0, Some(10), true),
+            (19, Some(30), true),
+            (12, Some(15), true),
+            (0, Some(99), true),
+            (0, Some(9), false),
+            (20, Some(29), false),
+            (15, Some(12), false),
+            (0, None, true),
+            (19, None, true),
+            (20, None, false),
+            (60, None, false),
+        ] {
+            assert_eq!(
+                r.overlaps_request(req_first, req_last),
+                expected,
+                "{req_first}-{req_last:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_within() {
+        let r = ContentRangeBytes {
+            first_byte: 10,
+            last_byte: 19,
+            complete_length: 50,
+        };
+        for (window, expected) in [
+            (10..=19, true),
+            (0..=49, true),
+            (0..=u64::MAX, true),
+            (11..=19, false),
+            (10..=18, false),
+            (15..=30, false),
+            (0..=12, false),
+            (20..=29, false),
+            (0..=9, false),
+            #[allow(clippy::reversed_empty_ranges)]
+            (19..=10, false),
+        ] {
+            assert_eq!(r.is_within(window.clone()), expected, "{window:?}");
+        }
+    }
+
+    #[test]
+    fn test_progress_percent() {
+        for (last_byte, complete_length, expected) in [
+            (0, 1000, 0),
+            (8, 1000, 0),
+            (9, 1000, 1),
+            (499, 1000, 50),
+            (498, 1000, 49),
+            (998, 1000, 99),
+            (999, 1000, 100),
+            (u64::MAX - 1, u64::MAX, 100),
+            (u64::MAX / 2, u64::MAX, 50),
+            (5, 0, 100),
+            (u64::MAX, 10, 100),
+        ] {
+            let r = ContentRangeBytes {
+                first_byte: 0,
+                last_byte,
+                complete_length,
+            };
+            assert_eq!(
+                r.progress_percent(),
+                expected,
+                "{last_byte}/{complete_length}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_content_length_u128() {
+        for (first_byte, last_byte, expected) in [
+            (0, 0, 1),
+            (10, 19, 10),
+            (0, u64::MAX - 1, 18_446_744_073_709_551_615),
+            (0, u64::MAX, 18_446_744_073_709_551_616),
+            (20, 19, 0),
+            (u64::MAX, 0, 0),
+        ] {
+            let r = ContentRangeBytes {
+                first_byte,
+                last_byte,
+                complete_length: u64::MAX,
+            };
+            assert_eq!(r.content_length_u128(), expected);
+        }
+    }
+
+    #[test]
+    fn test_relative_to() {
+        let r = ContentRangeBytes {
+            first_byte: 10,
+            last_byte: 19,
+            complete_length: 50,
+        };
+        let rebased = |first_byte, last_byte, complete_length| {
+            Some(ContentRangeBytes {
+                first_byte,
+                last_byte,
+                complete_length,
+            })
+        };
+        assert_eq!(r.relative_to(0), Some(r));
+        assert_eq!(r.relative_to(4), rebased(6, 15, 46));
+        assert_eq!(r.relative_to(10), rebased(0, 9, 40));
+        assert_eq!(r.relative_to(11), None);
+        assert_eq!(r.relative_to(u64::MAX), None);
+
+        // built by hand, bypassing validation
+        let r = ContentRangeBytes {
+            first_byte: 10,
+            last_byte: 5,
+            complete_length: 50,
+        };
+        assert_eq!(r.relative_to(8), None);
+        let r = ContentRangeBytes {
+            first_byte: 90,
+            last_byte: 150,
+            complete_length: 100,
+        };
+        assert_eq!(r.relative_to(10), None);
+    }
+
+    #[test]
+    fn test_align_to() {
+        let aligned = |first_byte, last_byte, complete_length, boundary| {
+            ContentRangeBytes {
+                first_byte,
+                last_byte,
+                complete_length,
+            }
+            .align_to(boundary)
+            .map(|r| (r.first_byte, r.last_byte, r.complete_length))
+        };
+        assert_eq!(aligned(0, 1023, 4096, 512), Some((0, 1023, 4096)));
+        assert_eq!(aligned(512, 1535, 4096, 512), Some((512, 1535, 4096)));
+        assert_eq!(aligned(600, 1100, 4096, 512), Some((512, 1535, 4096)));
+        assert_eq!(aligned(1024, 1024, 4096, 512), Some((1024, 1535, 4096)));
+        assert_eq!(aligned(600, 1100, 1200, 512), Some((512, 1199, 1200)));
+        assert_eq!(aligned(10, 19, 20, 1), Some((10, 19, 20)));
+        assert_eq!(
+            aligned(5, u64::MAX - 2, u64::MAX, 1 << 63),
+            Some((0, u64::MAX - 1, u64::MAX))
+        );
+        assert_eq!(aligned(10, 19, 20, 0), None);
+        assert_eq!(aligned(0, 0, 0, 512), None);
+        assert_eq!(aligned(90, 150, 100, 64), None);
+        assert_eq!(aligned(20, 10, 100, 16), None);
+    }
+
+    #[test]
+    fn test_scale_down() {
+        let scaled = |first_byte, last_byte, complete_length, block_size| {
+            ContentRangeBytes {
+                first_byte,
+                last_byte,
+                complete_length,
+            }
+            .scale_down(block_size)
+            .map(|r| (r.first_byte, r.last_byte, r.complete_length))
+        };
+        assert_eq!(scaled(0, 1023, 2048, 512), Some((0, 1, 4)));
+        assert_eq!(scaled(512, 1535, 2048, 512), Some((1, 2, 4)));
+        assert_eq!(scaled(600, 1100, 2000, 512), Some((1, 2, 4)));
+        assert_eq!(scaled(511, 512, 513, 512), Some((0, 1, 2)));
+        assert_eq!(scaled(0, 0, 1, 512), Some((0, 0, 1)));
+        assert_eq!(scaled(10, 19, 20, 1), Some((10, 19, 20)));
+        assert_eq!(
+            scaled(0, u64::MAX - 1, u64::MAX, 2),
+            Some((0, u64::MAX / 2, u64::MAX / 2 + 1))
+        );
+        assert_eq!(scaled(10, 19, 20, 0), None);
+    }
+
+    #[test]
+    fn test_le_bytes() {
+        for (first_byte, last_byte, complete_length) in
+            [(0, 0, 1), (10, 19, 50), (0, u64::MAX - 1, u64::MAX)]
+        {
+            let r = ContentRangeBytes {
+                first_byte,
+                last_byte,
+                complete_length,
+            };
+            assert_eq!(ContentRangeBytes::from_le_bytes(&r.to_le_bytes()), Some(r));
+        }
+
+        let mut buf = [0; 24];
+        buf[..8].copy_from_slice(&10_u64.to_le_bytes());
+        buf[8..16].copy_from_slice(&19_u64.to_le_bytes());
+        assert_eq!(ContentRangeBytes::from_le_bytes(&buf), None);
+        buf[16..].copy_from_slice(&50_u64.to_le_bytes());
+        assert!(ContentRangeBytes::from_le_bytes(&buf).is_some());
+        buf[..8].copy_from_slice(&20_u64.to_le_bytes());
+        assert_eq!(ContentRangeBytes::from_le_bytes(&buf), None);
+    }
+
+    #[test]
+    fn test_is_single_byte() {
+        for (value, expected) in [
+            (bytes(0, 0, 1), true),
+            (bytes(19, 19, 20), true),
+            (bytes(0, 1, 20), false),
+            (unbound(5, 5), true),
+            (unbound(5, 6), false),
+        ] {
+            let single = match value.unwrap() {
+                ContentRange::Bytes(r) => r.is_single_byte(),
+                ContentRange::UnboundBytes(r) => r.is_single_byte(),
+                ContentRange::Unsatisfied(_) => unreachable!(),
+            };
+            assert_eq!(single, expected, "{value:?}");
         }
     }
+
+    #[test]
+    fn test_infer_complete_length() {
+        let r = ContentRangeUnbound {
+            first_byte: 0,
+            last_byte: 9,
+        };
+        let expected = ContentRangeBytes {
+            first_byte: 0,
+            last_byte: 9,
+            complete_length: 10,
+        };
+        assert_eq!(r.infer_complete_length(10), Some(expected));
+        assert_eq!(r.infer_complete_length(5), None);
+        assert_eq!(r.infer_complete_length(9), None);
+        assert_eq!(r.infer_complete_length(11), None);
+        assert_eq!(r.infer_complete_length(20), None);
+        let r = ContentRangeUnbound {
+            first_byte: 5,
+            last_byte: 9,
+        };
+        assert_eq!(r.infer_complete_length(10), None);
+    }
+
+    #[test]
+    fn test_unsatisfied_justifies() {
+        let r = ContentRangeUnsatisfied {
+            complete_length: 20,
+        };
+        assert!(r.justifies(20));
+        assert!(r.justifies(100));
+        assert!(!r.justifies(0));
+        assert!(!r.justifies(19));
+    }
+
+    #[test]
+    fn test_empty_resource() {
+        let r = ContentRangeUnsatisfied { complete_length: 0 };
+        assert_eq!(
+            ContentRange::parse("bytes */0"),
+            Some(ContentRange::Unsatisfied(r))
+        );
+        // any request against an empty resource is legitimately unsatisfiable
+        assert!(r.justifies(0));
+        assert!(r.justifies(u64::MAX));
+    }
 }
@@ -0,0 +1,71 @@
+use crate::utils::is_whitespace;
+
+/// Options to relax or tighten parsing, see [`ContentRange::parse_with_options`].
+///
+/// The default options match [`ContentRange::parse`]. Override individual fields with
+/// `ParseOptions { range_separator: b"..", ..ParseOptions::default() }`.
+///
+/// Unless a different [`radix`](Self::radix) is explicitly chosen, numeric fields only ever
+/// consist of ASCII decimal digits, so values like `0x14` or `1e2` are always rejected.
+///
+/// [`ContentRange::parse`]: crate::ContentRange::parse
+/// [`ContentRange::parse_with_options`]: crate::ContentRange::parse_with_options
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::struct_excessive_bools)] // independent opt-in relaxations, not a state machine
+pub struct ParseOptions<'a> {
+    /// Separator between the first and the last byte positions, `-` by default.
+    pub range_separator: &'a [u8],
+    /// Largest value accepted for any numeric field, unlimited by default.
+    /// This is a policy guard against absurd values that still fit into `u64`.
+    pub max_value: Option<u64>,
+    /// Allow `,` or `_` between groups of three digits of numeric fields, e.g. `bytes 0-9/1,000`.
+    /// Each number must use a single kind of separator, and only its first group may be shorter.
+    /// Disabled by default.
+    pub allow_thousands_separators: bool,
+    /// Allow spaces and tabs around the `-` and `/` separators, e.g. `bytes 0 - 9 / 20`.
+    /// Enabled by default; disable it to follow the RFC grammar strictly.
+    pub allow_whitespace_around_separators: bool,
+    /// Allow a single `+` sign in front of each numeric field, e.g. `bytes +0-+9/+20`.
+    /// Disabled by default.
+    pub allow_leading_plus: bool,
+    /// Largest number of spaces and tabs accepted after the value, unlimited by default.
+    pub max_trailing_whitespace: Option<usize>,
+    /// Alternative unit names as `(alias, canonical)` pairs, e.g. `(b"octets", b"bytes")`.
+    /// A unit matching an alias is treated as its canonical unit, so only aliases of `bytes`
+    /// can be parsed. No aliases by default.
+    pub unit_aliases: &'a [(&'a [u8], &'a [u8])],
+    /// Largest number of digits accepted in each numeric field, including leading zeros.
+    /// Unlimited by default, although values must still fit into `u64`.
+    pub max_digits: Option<usize>,
+    /// Ignore a UTF-8 byte order mark (`EF BB BF`) in front of the value. Disabled by default.
+    pub strip_utf8_bom: bool,
+    /// Ignore a single line terminator (`\r\n` or `\n`) at the end of the value,
+    /// e.g. when it is read directly from a raw header line. Disabled by default.
+    pub strip_trailing_crlf: bool,
+    /// Radix of the numeric fields, from 2 to 16, e.g. 16 for `bytes a-f/10`.
+    /// Letter digits are case-insensitive. Any other radix fails every numeric field
+    /// with [`ParseError::UnsupportedRadix`](crate::ParseError::UnsupportedRadix).
+    /// Decimal (10) by default, as required by the RFC.
+    pub radix: u32,
+    /// Bytes treated as whitespace between the tokens, only space and tab by default.
+    pub whitespace: fn(u8) -> bool,
+}
+
+impl Default for ParseOptions<'_> {
+    fn default() -> Self {
+        Self {
+            range_separator: b"-",
+            max_value: None,
+            allow_thousands_separators: false,
+            allow_whitespace_around_separators: true,
+            allow_leading_plus: false,
+            max_trailing_whitespace: None,
+            unit_aliases: &[],
+            max_digits: None,
+            strip_utf8_bom: false,
+            strip_trailing_crlf: false,
+            radix: 10,
+            whitespace: is_whitespace,
+        }
+    }
+}
@@ -1,5 +1,7 @@
-use std::iter::Peekable;
-use std::slice::Iter;
+use crate::{Field, NumberError, ParseError, ParseOptions};
+
+/// Radixes supported by [`ParseOptions::radix`]
+const SUPPORTED_RADIX: std::ops::RangeInclusive<u32> = 2..=16;
 
 /// Helper method that returns None if test is true
 #[inline]
@@ -17,71 +19,226 @@ pub fn is_whitespace(c: u8) -> bool {
     c == b'\t' || c == b' '
 }
 
+/// Returns `true` if `value` is a non-empty RFC 7230 `token`, i.e. consists of `tchar` only
+pub fn is_token(value: &[u8]) -> bool {
+    !value.is_empty()
+        && value
+            .iter()
+            .all(|&c| c.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&c))
+}
+
 #[inline]
 fn into_digit(c: u8) -> u64 {
     u64::from(c - b'0')
 }
 
-pub(crate) trait IterExt {
-    #[must_use]
-    fn skip_spaces(&mut self) -> Option<u8>;
-    #[must_use]
-    fn parse_separator(&mut self, separator: u8) -> Option<u8>;
-    #[must_use]
-    fn parse_u64(&mut self) -> Option<u64>;
+/// Splits leading decimal digits off `input`, returning their value and the rest of the input.
+/// Returns `None` if there are no leading digits, or the value does not fit into `u64`.
+pub fn split_u64(input: &[u8]) -> Option<(u64, &[u8])> {
+    let digits = input.iter().take_while(|c| c.is_ascii_digit()).count();
+    fail_if(digits == 0)?;
+    let mut res: u64 = 0;
+    for &c in &input[..digits] {
+        res = res.checked_mul(10)?.checked_add(into_digit(c))?;
+    }
+    Some((res, &input[digits..]))
 }
 
-impl IterExt for Peekable<Iter<'_, u8>> {
-    /// Advances to the next non-blank byte, returning true if there is more data
-    fn skip_spaces(&mut self) -> Option<u8> {
-        loop {
-            match self.peek() {
-                None => return None,
-                Some(v) => {
-                    if is_whitespace(**v) {
-                        self.next();
-                    } else {
-                        return Some(**v);
-                    }
-                }
+/// Parses the whole `input` as a decimal number
+pub fn parse_u64_exact(input: &[u8]) -> Result<u64, NumberError> {
+    if input.is_empty() {
+        return Err(NumberError::Empty);
+    }
+    match split_u64(input) {
+        Some((value, [])) => Ok(value),
+        _ if input.iter().all(u8::is_ascii_digit) => Err(NumberError::Overflow),
+        _ => Err(NumberError::InvalidDigit),
+    }
+}
+
+/// Reads the header byte by byte, keeping track of the current position for error reporting
+pub(crate) struct Cursor<'a, 'o> {
+    input: &'a [u8],
+    pos: usize,
+    options: &'o ParseOptions<'o>,
+}
+
+impl<'a, 'o> Cursor<'a, 'o> {
+    /// Creates a cursor over `input`, starting at the `pos` offset
+    pub fn new(input: &'a [u8], pos: usize, options: &'o ParseOptions<'o>) -> Self {
+        Self {
+            input,
+            pos,
+            options,
+        }
+    }
+
+    /// Offset of the next byte within the input
+    #[inline]
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the next byte without consuming it
+    #[inline]
+    pub fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    /// Consumes the next byte
+    #[inline]
+    pub fn advance(&mut self) {
+        self.pos += 1;
+    }
+
+    /// Error describing the next byte as unexpected, or the end of input if there is nothing left
+    pub fn error(&self) -> ParseError {
+        match self.peek() {
+            None => ParseError::UnexpectedEnd,
+            Some(_) => ParseError::UnexpectedByte { offset: self.pos },
+        }
+    }
+
+    /// Advances to the next non-blank byte, returning it without consuming it
+    pub fn skip_spaces(&mut self) -> Option<u8> {
+        while let Some(v) = self.peek() {
+            if !(self.options.whitespace)(v) {
+                return Some(v);
             }
+            self.advance();
         }
+        None
     }
 
     /// Skip spaces, and ensure there is a given separator. Returns next non-space value
-    fn parse_separator(&mut self, separator: u8) -> Option<u8> {
-        if self.skip_spaces()? != separator {
-            return None;
+    /// Whitespace around the separator is only skipped if allowed by the options.
+    pub fn parse_separator(&mut self, separator: &[u8]) -> Result<u8, ParseError> {
+        let allow_spaces = self.options.allow_whitespace_around_separators;
+        if allow_spaces {
+            self.skip_spaces();
+        }
+        for &expected in separator {
+            if self.peek() != Some(expected) {
+                return Err(self.error());
+            }
+            self.advance();
         }
-        self.next()?; // consume separator
-        self.skip_spaces()
+        let next = if allow_spaces {
+            self.skip_spaces()
+        } else {
+            self.peek()
+        };
+        next.ok_or(ParseError::UnexpectedEnd)
     }
 
-    /// Consume u64 value
-    fn parse_u64(&mut self) -> Option<u64> {
-        let mut res = match self.next() {
-            None => return None,
-            Some(v) => {
-                let c = *v;
-                if !c.is_ascii_digit() {
-                    return None;
-                }
-                into_digit(*v)
-            }
+    /// Consume u64 value of the given field
+    pub fn parse_u64(&mut self, field: Field) -> Result<u64, ParseError> {
+        let start = self.pos;
+        if !SUPPORTED_RADIX.contains(&self.options.radix) {
+            return Err(ParseError::UnsupportedRadix {
+                radix: self.options.radix,
+            });
+        }
+        if self.options.allow_leading_plus && self.peek() == Some(b'+') {
+            self.advance();
+        }
+        let digits_start = self.pos;
+        let radix = u64::from(self.options.radix);
+        let mut res = match self.peek() {
+            None => return Err(ParseError::UnexpectedEnd),
+            Some(b'*') => return Err(ParseError::UnexpectedStar { offset: start }),
+            Some(c) => match self.digit(c) {
+                Some(v) => v,
+                None => return Err(ParseError::ExpectedNumber { offset: start }),
+            },
         };
-        loop {
-            match self.peek() {
-                None => return Some(res),
-                Some(v) => {
-                    let next = **v;
-                    if next.is_ascii_digit() {
-                        res = res.checked_mul(10)?.checked_add(into_digit(next))?;
-                        self.next();
-                    } else {
-                        return Some(res);
+        self.advance();
+        let mut digits = 1;
+        // the separator used by this number with the offset of its last use,
+        // and the number of digits after it
+        let mut separator = None;
+        let mut group_digits = 1;
+        if radix == 10 && res == 0 && matches!(self.peek(), Some(b'x' | b'X')) {
+            return Err(ParseError::HexNotAllowed { field });
+        }
+        while let Some(mut next) = self.peek() {
+            if self.options.allow_thousands_separators && (next == b',' || next == b'_') {
+                // only skip the separator if it is followed by another digit
+                match self.input.get(self.pos + 1) {
+                    Some(&c) if self.digit(c).is_some() => {
+                        // only the first group may be shorter than three digits,
+                        // and all groups must use the same separator
+                        let grouped = match separator {
+                            None => group_digits <= 3,
+                            Some((sep, _)) => sep == next && group_digits == 3,
+                        };
+                        if !grouped {
+                            return Err(ParseError::UnexpectedByte { offset: self.pos });
+                        }
+                        separator = Some((next, self.pos));
+                        group_digits = 0;
+                        self.advance();
+                        next = c;
                     }
+                    _ => break,
                 }
             }
+            let digit = match self.digit(next) {
+                Some(v) => v,
+                None => break,
+            };
+            res = match res.checked_mul(radix).and_then(|v| v.checked_add(digit)) {
+                Some(v) => v,
+                None => return Err(self.overflow_error(field, start, digits_start)),
+            };
+            digits += 1;
+            group_digits += 1;
+            self.advance();
+        }
+        if let Some((_, offset)) = separator {
+            if group_digits != 3 {
+                return Err(ParseError::UnexpectedByte { offset });
+            }
         }
+        if self.options.max_digits.map_or(false, |max| digits > max) {
+            return Err(ParseError::NumberTooLong { field, digits });
+        }
+        if self.options.max_value.map_or(false, |max| res > max) {
+            return Err(ParseError::NumberAboveMax { offset: start });
+        }
+        Ok(res)
+    }
+
+    /// Value of `c` as a digit in the configured radix, if it is one
+    #[inline]
+    fn digit(&self, c: u8) -> Option<u64> {
+        match self.options.radix {
+            10 => c.is_ascii_digit().then(|| into_digit(c)),
+            radix => char::from(c).to_digit(radix).map(u64::from),
+        }
+    }
+
+    /// Error for a number starting at `start` that does not fit into `u64`
+    fn overflow_error(&self, field: Field, start: usize, digits_start: usize) -> ParseError {
+        let digits = self.input[digits_start..]
+            .iter()
+            .take_while(|&&c| self.digit(c).is_some())
+            .count();
+        if digits > max_u64_digits(self.options.radix) {
+            ParseError::NumberTooLong { field, digits }
+        } else {
+            ParseError::NumberOverflow { offset: start }
+        }
+    }
+}
+
+/// Number of digits of `u64::MAX` in the given radix, longer numbers can never fit into `u64`
+fn max_u64_digits(radix: u32) -> usize {
+    let mut value = u64::MAX;
+    let mut digits = 0;
+    while value > 0 {
+        value /= u64::from(radix);
+        digits += 1;
     }
+    digits
 }